@@ -0,0 +1,534 @@
+//! Derive macro for implementing
+//! [`serde_db::de::DbValue`](https://docs.rs/serde_db/*/serde_db/de/trait.DbValue.html)
+//! on the value enum of a database driver.
+//!
+//! `#[derive(DbValue)]` implements `CanonicalDbValue` and `DbValue` for the enum, so that
+//! all `DbValueInto` conversions are provided by `serde_db`, and the numeric conversions
+//! follow the `ConversionPolicy` of the deserialization.
+//!
+//! ```rust,ignore
+//! #[macro_use]
+//! extern crate serde_db_derive;
+//!
+//! #[derive(DbValue)]
+//! pub enum MyDbValue<'buf> {
+//!     NULL,
+//!     TINYINT(u8),
+//!     SMALLINT(i16),
+//!     NullableSmallint(Option<i16>),
+//!     DOUBLE(f64),
+//!     #[db_value(conversion = "decimal")]
+//!     DECIMAL(BigDecimal),
+//!     #[db_value(rust_type = "i32")]
+//!     INT(DbInt),
+//!     STRING(String),
+//!     BorrowedString(&'buf str),
+//!     #[db_value(conversion = "display")]
+//!     TIMESTAMP(Timestamp),
+//!     #[db_value(conversion = "none")]
+//!     LOB(LobHandle),
+//! }
+//! ```
+//!
+//! Each variant must either be a unit variant, which represents the NULL value,
+//! or have exactly one unnamed field.
+//! Variants whose field is an `Option` are nullable: `None` is recognized as NULL.
+//!
+//! The conversion class of a variant defines its canonical representation,
+//! and is derived from the type of the field:
+//!
+//! | class        | field types                       | canonical value           |
+//! |--------------|-----------------------------------|---------------------------|
+//! | `"bool"`     | `bool`                            | `CanonicalValue::Bool`    |
+//! | `"signed"`   | `i8`, `i16`, `i32`, `i64`         | `CanonicalValue::I64`     |
+//! | `"unsigned"` | `u8`, `u16`, `u32`, `u64`         | `CanonicalValue::U64`     |
+//! | `"float"`    | `f32`, `f64`                      | `CanonicalValue::F64`     |
+//! | `"decimal"`  | any type that implements `Display`| `CanonicalValue::Decimal` |
+//! | `"string"`   | `String`, `&str`                  | `CanonicalValue::String`  |
+//! | `"display"`  | any type that implements `Display`| `CanonicalValue::String`  |
+//! | `"bytes"`    | `Vec<u8>`, `&[u8]`                | `CanonicalValue::Bytes`   |
+//! | `"none"`     | any type                          | conversions fail          |
+//!
+//! Variants with a field of type `&str` or `&[u8]` are additionally handed out with
+//! `DbValue::as_str()` or `DbValue::as_bytes()`, which allows deserializing them without
+//! an allocation.
+//!
+//! The variants can be annotated with `#[db_value(...)]`:
+//!
+//! * `nullable`: the field is an `Option`, although the field type does not show it,
+//!   e.g. because it is a type alias.
+//! * `rust_type = "..."`: the type from which the conversion class is derived,
+//!   if the field type is a type alias or a wrapper, like `rust_type = "Option<i16>"`;
+//!   the field value is converted into this type with `Into` before it is converted into
+//!   its canonical representation. Together with `nullable`, `rust_type` describes the value
+//!   within the `Option`.
+//! * `conversion = "..."`: the conversion class, see above.
+//!
+//! The field types must implement `Clone`: the derived `DbValue::canonical()` works on a
+//! reference and clones the field on every call, which for `String` and `Vec<u8>` fields
+//! copies the complete content. `serde_db` only calls it for conversions into numbers and
+//! `bool`; strings and bytes are converted with `CanonicalDbValue::into_canonical()`,
+//! which moves the field. The derived `DbValue::is_integer()` inspects the variant
+//! without calling `canonical()`.
+
+#![warn(missing_docs)]
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, Data, DeriveInput, Error, Fields, GenericArgument, Ident, Lit, Meta,
+    NestedMeta, PathArguments, Type,
+};
+
+/// Implements `CanonicalDbValue` and `DbValue` for an enum, see the crate documentation.
+#[proc_macro_derive(DbValue, attributes(db_value))]
+pub fn derive_db_value(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match impl_db_value(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+// The conversion class of a variant, which defines its canonical representation.
+#[derive(Clone, Copy, PartialEq)]
+enum Conversion {
+    Null,
+    Bool,
+    Signed,
+    Unsigned,
+    Float,
+    Decimal,
+    String,
+    Display,
+    Bytes,
+    None,
+}
+
+impl Conversion {
+    fn from_name(lit: &Lit) -> syn::Result<Conversion> {
+        let name = match *lit {
+            Lit::Str(ref s) => s.value(),
+            _ => return Err(Error::new_spanned(lit, "expected a string literal")),
+        };
+        Ok(match name.as_str() {
+            "bool" => Conversion::Bool,
+            "signed" => Conversion::Signed,
+            "unsigned" => Conversion::Unsigned,
+            "float" => Conversion::Float,
+            "decimal" => Conversion::Decimal,
+            "string" => Conversion::String,
+            "display" => Conversion::Display,
+            "bytes" => Conversion::Bytes,
+            "none" => Conversion::None,
+            _ => {
+                return Err(Error::new_spanned(
+                    lit,
+                    "unknown conversion, expected one of \"bool\", \"signed\", \"unsigned\", \
+                     \"float\", \"decimal\", \"string\", \"display\", \"bytes\", \"none\"",
+                ));
+            }
+        })
+    }
+
+    // Derives the conversion class from the rust type.
+    fn from_type(ty: &Type) -> Option<Conversion> {
+        match *ty {
+            Type::Reference(ref r) => match *r.elem {
+                Type::Slice(ref s) if is_type(&s.elem, "u8") => Some(Conversion::Bytes),
+                ref elem if is_type(elem, "str") => Some(Conversion::String),
+                _ => None,
+            },
+            Type::Paren(ref p) => Conversion::from_type(&p.elem),
+            Type::Group(ref g) => Conversion::from_type(&g.elem),
+            _ => match last_ident(ty)?.to_string().as_str() {
+                "bool" => Some(Conversion::Bool),
+                "i8" | "i16" | "i32" | "i64" => Some(Conversion::Signed),
+                "u8" | "u16" | "u32" | "u64" => Some(Conversion::Unsigned),
+                "f32" | "f64" => Some(Conversion::Float),
+                "String" => Some(Conversion::String),
+                "Vec" => match generic_argument(ty) {
+                    Some(arg) if is_type(arg, "u8") => Some(Conversion::Bytes),
+                    _ => None,
+                },
+                _ => None,
+            },
+        }
+    }
+
+    // The expression that converts the field value `v` into its canonical representation.
+    fn canonical_expr(self) -> TokenStream2 {
+        match self {
+            Conversion::Bool => quote!(::serde_db::de::CanonicalValue::Bool(v)),
+            Conversion::Signed => {
+                quote!(::serde_db::de::CanonicalValue::I64(::std::convert::From::from(v)))
+            }
+            Conversion::Unsigned => {
+                quote!(::serde_db::de::CanonicalValue::U64(::std::convert::From::from(v)))
+            }
+            Conversion::Float => {
+                quote!(::serde_db::de::CanonicalValue::F64(::std::convert::From::from(v)))
+            }
+            Conversion::Decimal => quote!(::serde_db::de::CanonicalValue::Decimal(
+                ::std::string::ToString::to_string(&v)
+            )),
+            Conversion::String => {
+                quote!(::serde_db::de::CanonicalValue::String(::std::string::String::from(v)))
+            }
+            Conversion::Display => quote!(::serde_db::de::CanonicalValue::String(
+                ::std::string::ToString::to_string(&v)
+            )),
+            Conversion::Bytes => {
+                quote!(::serde_db::de::CanonicalValue::Bytes(::std::vec::Vec::from(v)))
+            }
+            Conversion::Null | Conversion::None => unreachable!("no canonical expression"),
+        }
+    }
+}
+
+// The attributes of a variant.
+#[derive(Default)]
+struct VariantAttrs {
+    nullable: bool,
+    rust_type: Option<Type>,
+    conversion: Option<Conversion>,
+}
+
+impl VariantAttrs {
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<VariantAttrs> {
+        let mut result = VariantAttrs::default();
+        for attr in attrs {
+            if attr.path.segments.len() != 1 || attr.path.segments[0].ident != "db_value" {
+                continue;
+            }
+            let list = match attr.parse_meta()? {
+                Meta::List(list) => list,
+                meta => return Err(Error::new_spanned(meta, "expected #[db_value(...)]")),
+            };
+            for nested in list.nested {
+                match nested {
+                    NestedMeta::Meta(Meta::Word(ref ident)) if ident == "nullable" => {
+                        result.nullable = true;
+                    }
+                    NestedMeta::Meta(Meta::NameValue(ref nv)) if nv.ident == "rust_type" => {
+                        result.rust_type = Some(match nv.lit {
+                            Lit::Str(ref s) => s.parse()?,
+                            ref lit => {
+                                return Err(Error::new_spanned(lit, "expected a string literal"));
+                            }
+                        });
+                    }
+                    NestedMeta::Meta(Meta::NameValue(ref nv)) if nv.ident == "conversion" => {
+                        result.conversion = Some(Conversion::from_name(&nv.lit)?);
+                    }
+                    nested => {
+                        return Err(Error::new_spanned(
+                            nested,
+                            "unknown attribute, expected `nullable`, `rust_type = \"...\"`, \
+                             or `conversion = \"...\"`",
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+// A variant of the enum, with what the generated code needs to know about it.
+struct Variant<'a> {
+    ident: &'a Ident,
+    conversion: Conversion,
+    // the field is an Option
+    nullable: bool,
+    // the type into which the value is converted with Into, from `rust_type`;
+    // it is the type of the field value, or, if the field is an Option, of its content
+    rust_type: Option<Type>,
+    // the field value is converted into `rust_type`, which is an Option
+    nullable_rust_type: bool,
+    // the field is a reference, which is handed out with as_str() or as_bytes()
+    borrowed: bool,
+}
+
+impl<'a> Variant<'a> {
+    fn parse(variant: &'a syn::Variant) -> syn::Result<Variant<'a>> {
+        let attrs = VariantAttrs::parse(&variant.attrs)?;
+        let field_type = match variant.fields {
+            Fields::Unit => {
+                return Ok(Variant {
+                    ident: &variant.ident,
+                    conversion: Conversion::Null,
+                    nullable: false,
+                    rust_type: None,
+                    nullable_rust_type: false,
+                    borrowed: false,
+                });
+            }
+            Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => &fields.unnamed[0].ty,
+            _ => {
+                return Err(Error::new_spanned(
+                    variant,
+                    "DbValue can only be derived for unit variants \
+                     and variants with exactly one unnamed field",
+                ));
+            }
+        };
+        let ty = attrs.rust_type.as_ref().unwrap_or(field_type);
+        let (nullable, ty) = match last_ident(ty) {
+            Some(ident) if ident == "Option" => match generic_argument(ty) {
+                Some(inner) => (true, inner),
+                None => (attrs.nullable, ty),
+            },
+            _ => (attrs.nullable, ty),
+        };
+        // a `rust_type` that is an Option describes the complete field value
+        let nullable_rust_type = attrs.rust_type.is_some() && nullable && !attrs.nullable;
+        let conversion = match attrs.conversion {
+            Some(conversion) => conversion,
+            None => Conversion::from_type(ty).ok_or_else(|| {
+                Error::new_spanned(
+                    ty,
+                    "the conversion of this type cannot be derived, \
+                     use #[db_value(rust_type = \"...\")] or #[db_value(conversion = \"...\")]",
+                )
+            })?,
+        };
+        let rust_type = match attrs.rust_type {
+            Some(ref rust_type) if nullable_rust_type => Some(rust_type.clone()),
+            Some(_) => Some(ty.clone()),
+            None => None,
+        };
+        Ok(Variant {
+            ident: &variant.ident,
+            conversion,
+            nullable: nullable && !nullable_rust_type,
+            rust_type,
+            nullable_rust_type,
+            borrowed: attrs.rust_type.is_none() && matches!(*ty, Type::Reference(_)),
+        })
+    }
+}
+
+fn impl_db_value(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let data = match input.data {
+        Data::Enum(ref data) => data,
+        _ => return Err(Error::new_spanned(input, "DbValue can only be derived for enums")),
+    };
+    let variants = data
+        .variants
+        .iter()
+        .map(Variant::parse)
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut into_canonical_arms = Vec::new();
+    let mut canonical_arms = Vec::new();
+    let mut is_null_arms = Vec::new();
+    let mut is_integer_arms = Vec::new();
+    let mut as_str_arms = Vec::new();
+    let mut as_bytes_arms = Vec::new();
+    for variant in &variants {
+        let ident = variant.ident;
+        match variant.conversion {
+            Conversion::Null => {
+                into_canonical_arms
+                    .push(quote!(#name::#ident => Ok(::serde_db::de::CanonicalValue::Null)));
+                canonical_arms
+                    .push(quote!(#name::#ident => Some(::serde_db::de::CanonicalValue::Null)));
+                is_null_arms.push(quote!(#name::#ident => true));
+            }
+            Conversion::None => {
+                let msg = format!("{}::{} has no canonical representation", name, ident);
+                into_canonical_arms.push(quote!(#name::#ident(_) => Err(
+                    ::serde_db::de::ConversionError::ValueType(#msg.to_string())
+                )));
+                canonical_arms.push(quote!(#name::#ident(_) => None));
+            }
+            conversion => {
+                let expr = conversion.canonical_expr();
+                let (pattern, null_pattern) = if variant.nullable {
+                    (quote!(#name::#ident(Some(v))), Some(quote!(#name::#ident(None))))
+                } else {
+                    (quote!(#name::#ident(v)), None)
+                };
+                if let Some(ref null_pattern) = null_pattern {
+                    into_canonical_arms
+                        .push(quote!(#null_pattern => Ok(::serde_db::de::CanonicalValue::Null)));
+                    canonical_arms
+                        .push(quote!(#null_pattern => Some(::serde_db::de::CanonicalValue::Null)));
+                    is_null_arms.push(quote!(#null_pattern => true));
+                }
+                let into_rust_type = match variant.rust_type {
+                    Some(ref rust_type) => {
+                        quote!(let v: #rust_type = ::std::convert::Into::into(v);)
+                    }
+                    None => quote!(),
+                };
+                if variant.nullable_rust_type {
+                    into_canonical_arms.push(quote!(#pattern => {
+                        #into_rust_type
+                        match v {
+                            Some(v) => Ok(#expr),
+                            None => Ok(::serde_db::de::CanonicalValue::Null),
+                        }
+                    }));
+                    canonical_arms.push(quote!(#pattern => {
+                        let v = ::std::clone::Clone::clone(v);
+                        #into_rust_type
+                        match v {
+                            Some(v) => Some(#expr),
+                            None => Some(::serde_db::de::CanonicalValue::Null),
+                        }
+                    }));
+                    is_null_arms.push(quote!(#name::#ident(ref v) => {
+                        let v = ::std::clone::Clone::clone(v);
+                        #into_rust_type
+                        v.is_none()
+                    }));
+                } else {
+                    into_canonical_arms.push(quote!(#pattern => {
+                        #into_rust_type
+                        Ok(#expr)
+                    }));
+                    canonical_arms.push(quote!(#pattern => {
+                        let v = ::std::clone::Clone::clone(v);
+                        #into_rust_type
+                        Some(#expr)
+                    }));
+                }
+                if conversion == Conversion::Signed || conversion == Conversion::Unsigned {
+                    is_integer_arms.push(match null_pattern {
+                        Some(null_pattern) => quote!(#null_pattern => false),
+                        None if variant.nullable_rust_type => quote!(#name::#ident(ref v) => {
+                            let v = ::std::clone::Clone::clone(v);
+                            #into_rust_type
+                            v.is_some()
+                        }),
+                        None => quote!(#name::#ident(_) => true),
+                    });
+                    if variant.nullable {
+                        is_integer_arms.push(quote!(#name::#ident(_) => true));
+                    }
+                }
+                if variant.borrowed {
+                    match conversion {
+                        Conversion::String => as_str_arms.push(quote!(#pattern => Some(v))),
+                        Conversion::Bytes => as_bytes_arms.push(quote!(#pattern => Some(v))),
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+
+    let as_str = if as_str_arms.is_empty() {
+        quote!()
+    } else {
+        quote! {
+            fn as_str<'de>(&self) -> ::std::option::Option<&'de str>
+            where
+                Self: 'de,
+            {
+                match *self {
+                    #(#as_str_arms,)*
+                    _ => None,
+                }
+            }
+        }
+    };
+    let as_bytes = if as_bytes_arms.is_empty() {
+        quote!()
+    } else {
+        quote! {
+            fn as_bytes<'de>(&self) -> ::std::option::Option<&'de [u8]>
+            where
+                Self: 'de,
+            {
+                match *self {
+                    #(#as_bytes_arms,)*
+                    _ => None,
+                }
+            }
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::serde_db::de::CanonicalDbValue for #name #ty_generics
+        #where_clause
+        {
+            fn into_canonical(
+                self,
+            ) -> ::std::result::Result<
+                ::serde_db::de::CanonicalValue,
+                ::serde_db::de::ConversionError,
+            > {
+                match self {
+                    #(#into_canonical_arms,)*
+                }
+            }
+        }
+
+        impl #impl_generics ::serde_db::de::DbValue for #name #ty_generics #where_clause {
+            #[allow(unreachable_patterns)]
+            fn is_null(&self) -> bool {
+                match *self {
+                    #(#is_null_arms,)*
+                    _ => false,
+                }
+            }
+
+            fn canonical(&self) -> ::std::option::Option<::serde_db::de::CanonicalValue> {
+                match self {
+                    #(#canonical_arms,)*
+                }
+            }
+
+            #[allow(unreachable_patterns)]
+            fn is_integer(&self) -> bool {
+                match *self {
+                    #(#is_integer_arms,)*
+                    _ => false,
+                }
+            }
+
+            #as_str
+
+            #as_bytes
+        }
+    })
+}
+
+// The last identifier of a type path, like `Option` for `std::option::Option<i16>`.
+fn last_ident(ty: &Type) -> Option<&Ident> {
+    match *ty {
+        Type::Path(ref p) if p.qself.is_none() => p.path.segments.last().map(|s| &s.value().ident),
+        _ => None,
+    }
+}
+
+// The single generic type argument of a type path, like `i16` for `Option<i16>`.
+fn generic_argument(ty: &Type) -> Option<&Type> {
+    let segment = match *ty {
+        Type::Path(ref p) => p.path.segments.last()?.into_value(),
+        _ => return None,
+    };
+    match segment.arguments {
+        PathArguments::AngleBracketed(ref args) if args.args.len() == 1 => match args.args[0] {
+            GenericArgument::Type(ref ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn is_type(ty: &Type, name: &str) -> bool {
+    match last_ident(ty) {
+        Some(ident) => ident == name,
+        None => false,
+    }
+}
@@ -0,0 +1,51 @@
+//! Ready-made checks for the implementations of
+//! [`DbValue`](../de/trait.DbValue.html),
+//! [`DeserializableResultset`](../de/trait.DeserializableResultset.html), and
+//! [`DbvFactory`](../ser/trait.DbvFactory.html) in a database driver.
+//!
+//! This module is only available with the feature `conformance`, which a driver
+//! would usually only activate for its tests:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! serde_db = { version = "0.8", features = ["conformance"] }
+//! ```
+//!
+//! Each check returns a [`Report`](struct.Report.html) with a compatibility matrix,
+//! which shows for each value or parameter of the driver which rust types are supported.
+//! The reports can be merged, printed, and asserted:
+//!
+//! ```ignore
+//! #[test]
+//! fn conformance() {
+//!     let report = ValueChecks::new()
+//!         .value("INT 42", || MyDbValue::INT(42), CanonicalValue::I64(42))
+//!         .value(
+//!             "NVARCHAR abc",
+//!             || MyDbValue::NVARCHAR("abc".into()),
+//!             CanonicalValue::String("abc".into()),
+//!         )
+//!         .null("NULL INT", || MyDbValue::N_INT(None))
+//!         .run()
+//!         .merge(check_cardinality(|rows| my_resultset_with_rows(rows)))
+//!         .merge(ParamChecks::new().parameter("INT", my_int_parameter()).run());
+//!     println!("{}", report);
+//!     report.assert_conformant();
+//! }
+//! ```
+//!
+//! Conversions that a driver does not support are not regarded as failures;
+//! a check fails if a conversion gives a wrong result, if a value is converted although
+//! it exceeds the range of the target type, if NULL values are not handled correctly,
+//! if the cardinality rules for resultsets are violated,
+//! or if a rust value does not survive the round trip through a parameter.
+
+mod cardinality_checks;
+mod param_checks;
+mod report;
+mod value_checks;
+
+pub use self::cardinality_checks::check_cardinality;
+pub use self::param_checks::ParamChecks;
+pub use self::report::{Matrix, Outcome, Report};
+pub use self::value_checks::ValueChecks;
@@ -0,0 +1,75 @@
+use std::fmt;
+
+use crate::conformance::{Matrix, Outcome, Report};
+use crate::de::{DeserializableResultset, DeserializationOptions, RowCardinality};
+
+/// Checks that the driver's resultsets follow the cardinality rules of `serde_db`,
+/// with the matrix "Resultset cardinality".
+///
+/// `new_resultset` must create a resultset with a single column and the given number of rows,
+/// whose values can be converted into `i64`.
+///
+/// ```ignore
+/// let report = serde_db::conformance::check_cardinality(|rows| {
+///     MyResultset::new(&["ID"], (0..rows).map(|i| vec![MyDbValue::INT(i as i32)]))
+/// });
+/// ```
+pub fn check_cardinality<RS, F>(new_resultset: F) -> Report
+where
+    RS: DeserializableResultset,
+    F: Fn(usize) -> RS,
+{
+    let exactly_one = DeserializationOptions::default().cardinality(RowCardinality::ExactlyOne);
+    let first_row = DeserializationOptions::default().cardinality(RowCardinality::FirstRow);
+
+    let mut matrix = Matrix::new("Resultset cardinality", &["result"]);
+    let mut push = |label: &str, outcome: Outcome| matrix.push(label, vec![outcome]);
+
+    push("0 rows into a value", expect_err(new_resultset(0).into_typed::<i64>()));
+    push("1 row into a value", expect(new_resultset(1).into_typed::<i64>(), |_| true));
+    push("2 rows into a value", expect_err(new_resultset(2).into_typed::<i64>()));
+    push(
+        "0 rows into an Option",
+        expect(new_resultset(0).into_typed::<Option<i64>>(), Option::is_none),
+    );
+    push(
+        "1 row into an Option",
+        expect(new_resultset(1).into_typed::<Option<i64>>(), Option::is_some),
+    );
+    push(
+        "2 rows into an Option",
+        expect_err(new_resultset(2).into_typed::<Option<i64>>()),
+    );
+    push(
+        "0 rows into a Vec",
+        expect(new_resultset(0).into_typed::<Vec<i64>>(), Vec::is_empty),
+    );
+    push(
+        "2 rows into a Vec",
+        expect(new_resultset(2).into_typed::<Vec<i64>>(), |v| v.len() == 2),
+    );
+    push(
+        "0 rows into an Option, ExactlyOne",
+        expect_err(new_resultset(0).into_typed_with::<Option<i64>>(&exactly_one)),
+    );
+    push(
+        "2 rows into a value, FirstRow",
+        expect(new_resultset(2).into_typed_with::<i64>(&first_row), |_| true),
+    );
+    Report::new(matrix)
+}
+
+fn expect<T: fmt::Debug, E, F: Fn(&T) -> bool>(result: Result<T, E>, is_expected: F) -> Outcome {
+    match result {
+        Ok(ref value) if is_expected(value) => Outcome::Supported,
+        Ok(value) => Outcome::Failed(format!("unexpected result {:?}", value)),
+        Err(_) => Outcome::Failed("unexpected error".to_string()),
+    }
+}
+
+fn expect_err<T: fmt::Debug, E>(result: Result<T, E>) -> Outcome {
+    match result {
+        Ok(value) => Outcome::Failed(format!("{:?} instead of an error", value)),
+        Err(_) => Outcome::Rejected,
+    }
+}
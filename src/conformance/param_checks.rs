@@ -0,0 +1,136 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt;
+use std::iter;
+
+use crate::conformance::value_checks::ByteBuf;
+use crate::conformance::{Matrix, Outcome, Report};
+use crate::de::DbValue;
+use crate::ser::{to_params, DbvFactory};
+
+const COLUMNS: [&str; 16] = [
+    "bool", "u8", "u16", "u32", "u64", "u128", "i8", "i16", "i32", "i64", "i128", "f32", "f64",
+    "String", "bytes", "NULL",
+];
+
+/// Checks the round trip of rust values through the driver's parameters,
+/// with the matrix "Parameters".
+///
+/// Each rust value that a parameter accepts with `to_params()`, and that can be converted back
+/// into its rust type, must be converted back into the same rust value;
+/// `None` must be converted into a NULL value.
+///
+/// ```ignore
+/// let report = ParamChecks::new()
+///     .parameter("SMALLINT", MyParameterDescriptor::new(TypeId::SMALLINT))
+///     .parameter("NVARCHAR", MyParameterDescriptor::new(TypeId::NVARCHAR))
+///     .run();
+/// ```
+pub struct ParamChecks<DF> {
+    parameters: Vec<(String, DF)>,
+}
+
+impl<DF> Default for ParamChecks<DF> {
+    fn default() -> ParamChecks<DF> {
+        ParamChecks {
+            parameters: Vec::new(),
+        }
+    }
+}
+
+impl<DF> ParamChecks<DF>
+where
+    DF: DbvFactory + Clone,
+    DF::DBV: DbValue,
+{
+    /// Creates an empty set of checks.
+    pub fn new() -> ParamChecks<DF> {
+        ParamChecks::default()
+    }
+
+    /// Adds a parameter.
+    pub fn parameter<S: Into<String>>(mut self, label: S, factory: DF) -> ParamChecks<DF> {
+        self.parameters.push((label.into(), factory));
+        self
+    }
+
+    /// Runs the checks.
+    pub fn run(&self) -> Report {
+        let mut matrix = Matrix::new("Parameters", &COLUMNS);
+        for (label, df) in &self.parameters {
+            matrix.push(
+                label.as_str(),
+                vec![
+                    check_round_trip(df, true),
+                    check_round_trip(df, 8_u8),
+                    check_round_trip(df, 16_u16),
+                    check_round_trip(df, 32_u32),
+                    check_round_trip(df, 64_u64),
+                    check_round_trip(df, 128_u128),
+                    check_round_trip(df, -8_i8),
+                    check_round_trip(df, -16_i16),
+                    check_round_trip(df, -32_i32),
+                    check_round_trip(df, -64_i64),
+                    check_round_trip(df, -128_i128),
+                    check_round_trip(df, 1.5_f32),
+                    check_round_trip(df, 2.5_f64),
+                    check_round_trip(df, "abc".to_string()),
+                    check_round_trip(df, ByteBuf(b"abc".to_vec())),
+                    check_null_round_trip(df),
+                ],
+            );
+        }
+        Report::new(matrix)
+    }
+}
+
+fn check_round_trip<DF, T>(df: &DF, value: T) -> Outcome
+where
+    DF: DbvFactory + Clone,
+    DF::DBV: DbValue,
+    T: Serialize + DeserializeOwned + PartialEq + fmt::Debug,
+{
+    let dbv = match serialize(df, &value) {
+        Ok(dbv) => dbv,
+        Err(outcome) => return outcome,
+    };
+    match dbv.into_typed::<T>() {
+        Ok(ref result) if *result == value => Outcome::Supported,
+        Ok(result) => Outcome::Failed(format!("{:?} is converted back into {:?}", value, result)),
+        Err(_) => Outcome::Unsupported,
+    }
+}
+
+fn check_null_round_trip<DF>(df: &DF) -> Outcome
+where
+    DF: DbvFactory + Clone,
+    DF::DBV: DbValue,
+{
+    let dbv = match serialize(df, &None::<i64>) {
+        Ok(dbv) => dbv,
+        Err(outcome) => return outcome,
+    };
+    if !dbv.is_null() {
+        return Outcome::Failed("None is not serialized into a NULL value".to_string());
+    }
+    match dbv.into_typed::<Option<i64>>() {
+        Ok(None) => Outcome::Supported,
+        result => Outcome::Failed(format!("None is converted back into {:?}", result)),
+    }
+}
+
+// Serializes the value into a single parameter; a rejected value is not supported.
+fn serialize<DF, T>(df: &DF, value: &T) -> Result<DF::DBV, Outcome>
+where
+    DF: DbvFactory + Clone,
+    T: Serialize,
+{
+    let mut dbvs = to_params(&(value,), &mut iter::once(df.clone()))
+        .map_err(|_| Outcome::Unsupported)?;
+    match (dbvs.pop(), dbvs.is_empty()) {
+        (Some(dbv), true) => Ok(dbv),
+        _ => Err(Outcome::Failed(
+            "the value is not serialized into a single parameter".to_string(),
+        )),
+    }
+}
@@ -0,0 +1,188 @@
+use std::fmt;
+
+/// The outcome of a single check.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Outcome {
+    /// The conversion succeeded with the expected result.
+    Supported,
+    /// The conversion failed, although it is possible; the driver does not support it.
+    Unsupported,
+    /// The conversion failed, as expected, e.g. with a range error.
+    Rejected,
+    /// The conversion succeeded, although the conversion of the canonical value
+    /// fails; the driver is more flexible than `serde_db`, the result is not checked.
+    Extended,
+    /// The check failed; the driver does not conform.
+    Failed(String),
+}
+
+impl Outcome {
+    /// Returns true if the check failed.
+    pub fn is_failed(&self) -> bool {
+        matches!(*self, Outcome::Failed(_))
+    }
+
+    fn symbol(&self) -> &'static str {
+        match *self {
+            Outcome::Supported => "ok",
+            Outcome::Unsupported => "-",
+            Outcome::Rejected => "x",
+            Outcome::Extended => "+",
+            Outcome::Failed(_) => "FAIL",
+        }
+    }
+}
+
+/// The outcomes of a group of checks, with one row per value, parameter, or scenario,
+/// and one column per rust type.
+#[derive(Clone, Debug)]
+pub struct Matrix {
+    title: String,
+    columns: Vec<String>,
+    rows: Vec<(String, Vec<Outcome>)>,
+}
+
+impl Matrix {
+    pub(crate) fn new<S: Into<String>>(title: S, columns: &[&str]) -> Matrix {
+        Matrix {
+            title: title.into(),
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    pub(crate) fn push<S: Into<String>>(&mut self, label: S, outcomes: Vec<Outcome>) {
+        debug_assert_eq!(outcomes.len(), self.columns.len());
+        self.rows.push((label.into(), outcomes));
+    }
+
+    /// The title of the matrix.
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// The column labels.
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// The row labels, with the outcomes of the row.
+    pub fn rows(&self) -> &[(String, Vec<Outcome>)] {
+        &self.rows
+    }
+
+    /// Returns the outcome for the given row and column label.
+    pub fn get(&self, row: &str, column: &str) -> Option<&Outcome> {
+        let col_idx = self.columns.iter().position(|c| c == column)?;
+        self.rows
+            .iter()
+            .find(|&(label, _)| label == row)
+            .map(|(_, outcomes)| &outcomes[col_idx])
+    }
+
+    fn failures(&self) -> Vec<String> {
+        let mut failures = Vec::new();
+        for (label, outcomes) in &self.rows {
+            for (column, outcome) in self.columns.iter().zip(outcomes) {
+                if let Outcome::Failed(ref reason) = *outcome {
+                    failures.push(format!("{}: {} / {}: {}", self.title, label, column, reason));
+                }
+            }
+        }
+        failures
+    }
+}
+
+impl fmt::Display for Matrix {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(fmt, "{}", self.title)?;
+        let label_width = self.rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+        let widths: Vec<usize> = self
+            .columns
+            .iter()
+            .map(|column| std::cmp::max(column.len(), "FAIL".len()))
+            .collect();
+        write!(fmt, "{:width$}", "", width = label_width)?;
+        for (column, width) in self.columns.iter().zip(&widths) {
+            write!(fmt, " {:>width$}", column, width = width)?;
+        }
+        writeln!(fmt)?;
+        for (label, outcomes) in &self.rows {
+            write!(fmt, "{:width$}", label, width = label_width)?;
+            for (outcome, width) in outcomes.iter().zip(&widths) {
+                write!(fmt, " {:>width$}", outcome.symbol(), width = width)?;
+            }
+            writeln!(fmt)?;
+        }
+        Ok(())
+    }
+}
+
+/// The result of a conformance check, a compatibility matrix per group of checks.
+///
+/// The `Display` implementation prints the matrices, with
+/// `ok` for supported conversions, `-` for unsupported conversions,
+/// `x` for conversions that are rejected as expected,
+/// `+` for conversions that go beyond those of `serde_db`,
+/// and `FAIL` for failed checks, which are listed below the matrices.
+#[derive(Clone, Debug, Default)]
+pub struct Report {
+    matrices: Vec<Matrix>,
+}
+
+impl Report {
+    pub(crate) fn new(matrix: Matrix) -> Report {
+        Report {
+            matrices: vec![matrix],
+        }
+    }
+
+    /// Adds the matrices of another report.
+    pub fn merge(mut self, other: Report) -> Report {
+        self.matrices.extend(other.matrices);
+        self
+    }
+
+    /// The compatibility matrices.
+    pub fn matrices(&self) -> &[Matrix] {
+        &self.matrices
+    }
+
+    /// Returns the matrix with the given title.
+    pub fn matrix(&self, title: &str) -> Option<&Matrix> {
+        self.matrices.iter().find(|m| m.title == title)
+    }
+
+    /// Describes all failed checks.
+    pub fn failures(&self) -> Vec<String> {
+        self.matrices.iter().flat_map(Matrix::failures).collect()
+    }
+
+    /// Returns true if no check failed.
+    pub fn is_conformant(&self) -> bool {
+        self.matrices
+            .iter()
+            .all(|m| m.rows.iter().all(|(_, outcomes)| !outcomes.iter().any(Outcome::is_failed)))
+    }
+
+    /// Panics with the report if a check failed, for use in the tests of a driver.
+    pub fn assert_conformant(&self) {
+        assert!(self.is_conformant(), "Driver does not conform to serde_db:\n{}", self);
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        for matrix in &self.matrices {
+            writeln!(fmt, "{}", matrix)?;
+        }
+        let failures = self.failures();
+        if !failures.is_empty() {
+            writeln!(fmt, "Failures:")?;
+            for failure in failures {
+                writeln!(fmt, "  {}", failure)?;
+            }
+        }
+        Ok(())
+    }
+}
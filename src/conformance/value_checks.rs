@@ -0,0 +1,352 @@
+use serde::de::{Deserialize, DeserializeOwned, Deserializer, Visitor};
+use std::fmt;
+
+use crate::conformance::{Matrix, Outcome, Report};
+use crate::de::{
+    CanonicalValue, ConversionError, ConversionPolicy, DbValue, DbValueInto, FromCanonical,
+};
+
+const COLUMNS: [&str; 18] = [
+    "is_null", "canonical", "bool", "u8", "u16", "u32", "u64", "u128", "i8", "i16", "i32", "i64",
+    "i128", "f32", "f64", "String", "bytes", "&str",
+];
+
+/// Checks the conversions of the driver's values into all supported rust types.
+///
+/// Each value is registered with a constructor and with its expected content, as
+/// `CanonicalValue`. Its conversion into a rust type with the driver's `DbValueInto`
+/// must give the same result as the conversion of the canonical value; if the canonical
+/// value does not fit into the rust type, the conversion must fail with
+/// `ConversionError::NumberRange`.
+/// The deserialization of the value must agree with `DbValueInto`, also if the driver
+/// implements [`DbValue::canonical()`](../de/trait.DbValue.html#method.canonical),
+/// which then must return the registered canonical value.
+/// Each value must also be converted into `Some(...)` if the target is an `Option`.
+/// The conversion into the rust type that matches the canonical value, like `i64` for
+/// `CanonicalValue::I64`, must be supported; other targets are optional.
+///
+/// NULL values must be converted into `None` if the target is an `Option`,
+/// and must not be converted if the target is no `Option`.
+///
+/// ```ignore
+/// let report = ValueChecks::new()
+///     .value("SMALLINT 42", || MyDbValue::SMALLINT(42), CanonicalValue::I64(42))
+///     .value("DECIMAL 12.50", || MyDbValue::DECIMAL(..), CanonicalValue::Decimal("12.50".into()))
+///     .null("NULL SMALLINT", || MyDbValue::N_SMALLINT(None))
+///     .run();
+/// ```
+pub struct ValueChecks<V> {
+    values: Vec<(String, Constructor<V>, CanonicalValue)>,
+    nulls: Vec<(String, Constructor<V>)>,
+}
+
+type Constructor<V> = Box<dyn Fn() -> V>;
+
+impl<V: DbValue> Default for ValueChecks<V> {
+    fn default() -> ValueChecks<V> {
+        ValueChecks {
+            values: Vec::new(),
+            nulls: Vec::new(),
+        }
+    }
+}
+
+impl<V: DbValue> ValueChecks<V> {
+    /// Creates an empty set of checks.
+    pub fn new() -> ValueChecks<V> {
+        ValueChecks::default()
+    }
+
+    /// Adds a value, with the constructor of the value and its expected content.
+    pub fn value<S, F>(mut self, label: S, new_value: F, expected: CanonicalValue) -> ValueChecks<V>
+    where
+        S: Into<String>,
+        F: Fn() -> V + 'static,
+    {
+        self.values.push((label.into(), Box::new(new_value), expected));
+        self
+    }
+
+    /// Adds a NULL value, with its constructor.
+    pub fn null<S, F>(mut self, label: S, new_value: F) -> ValueChecks<V>
+    where
+        S: Into<String>,
+        F: Fn() -> V + 'static,
+    {
+        self.nulls.push((label.into(), Box::new(new_value)));
+        self
+    }
+
+    /// Runs the checks, with the matrix "Values".
+    pub fn run(&self) -> Report {
+        let mut matrix = Matrix::new("Values", &COLUMNS);
+        for (label, new_value, expected) in &self.values {
+            let new_value = &**new_value;
+            let is_null = if new_value().is_null() {
+                Outcome::Failed("is_null() returns true".to_string())
+            } else {
+                Outcome::Supported
+            };
+            let canonical = match new_value().canonical() {
+                None => Outcome::Unsupported,
+                Some(ref canonical) if canonical == expected => Outcome::Supported,
+                Some(canonical) => Outcome::Failed(format!("canonical() returns {:?}", canonical)),
+            };
+            matrix.push(
+                label.as_str(),
+                vec![
+                    is_null,
+                    canonical,
+                    check_value::<V, bool>(new_value, expected, "bool"),
+                    check_value::<V, u8>(new_value, expected, "u8"),
+                    check_value::<V, u16>(new_value, expected, "u16"),
+                    check_value::<V, u32>(new_value, expected, "u32"),
+                    check_value::<V, u64>(new_value, expected, "u64"),
+                    check_value::<V, u128>(new_value, expected, "u128"),
+                    check_value::<V, i8>(new_value, expected, "i8"),
+                    check_value::<V, i16>(new_value, expected, "i16"),
+                    check_value::<V, i32>(new_value, expected, "i32"),
+                    check_value::<V, i64>(new_value, expected, "i64"),
+                    check_value::<V, i128>(new_value, expected, "i128"),
+                    check_value::<V, f32>(new_value, expected, "f32"),
+                    check_value::<V, f64>(new_value, expected, "f64"),
+                    check_value::<V, String>(new_value, expected, "String"),
+                    check_value::<V, ByteBuf>(new_value, expected, "bytes"),
+                    check_borrowed_str(new_value, expected),
+                ],
+            );
+        }
+        for (label, new_value) in &self.nulls {
+            let new_value = &**new_value;
+            let is_null = if new_value().is_null() {
+                Outcome::Supported
+            } else {
+                Outcome::Failed("is_null() returns false".to_string())
+            };
+            let canonical = match new_value().canonical() {
+                None => Outcome::Unsupported,
+                Some(CanonicalValue::Null) => Outcome::Supported,
+                Some(canonical) => Outcome::Failed(format!("canonical() returns {:?}", canonical)),
+            };
+            matrix.push(
+                label.as_str(),
+                vec![
+                    is_null,
+                    canonical,
+                    check_null::<V, bool>(new_value),
+                    check_null::<V, u8>(new_value),
+                    check_null::<V, u16>(new_value),
+                    check_null::<V, u32>(new_value),
+                    check_null::<V, u64>(new_value),
+                    check_null::<V, u128>(new_value),
+                    check_null::<V, i8>(new_value),
+                    check_null::<V, i16>(new_value),
+                    check_null::<V, i32>(new_value),
+                    check_null::<V, i64>(new_value),
+                    check_null::<V, i128>(new_value),
+                    check_null::<V, f32>(new_value),
+                    check_null::<V, f64>(new_value),
+                    check_null::<V, String>(new_value),
+                    check_null::<V, ByteBuf>(new_value),
+                    match new_value().as_str() {
+                        Some(s) => Outcome::Failed(format!("as_str() returns {:?}", s)),
+                        None => Outcome::Rejected,
+                    },
+                ],
+            );
+        }
+        Report::new(matrix)
+    }
+}
+
+// Compares the conversion of the driver with the conversion of the canonical value,
+// and the deserialization with the conversion of the driver.
+fn check_value<V, T>(
+    new_value: &dyn Fn() -> V,
+    expected: &CanonicalValue,
+    column: &'static str,
+) -> Outcome
+where
+    V: DbValue,
+    T: DeserializeOwned + FromCanonical + FromDbValue<V> + PartialEq + fmt::Debug,
+{
+    let native = native_column(expected) == Some(column);
+    let expected = T::from_canonical(expected.clone(), ConversionPolicy::default());
+    let converted = T::from_db_value(new_value());
+    match (new_value().into_typed::<T>(), &converted) {
+        (Ok(ref deserialized), Ok(ref converted)) if deserialized == converted => {}
+        (Err(_), Err(_)) => {}
+        (deserialized, _) => {
+            return Outcome::Failed(format!(
+                "deserialized into {:?}, but converted into {:?} by DbValueInto",
+                deserialized, converted
+            ));
+        }
+    }
+    let actual = match converted {
+        Ok(actual) => actual,
+        Err(e) => {
+            let reason = e.to_string();
+            return match (expected, e) {
+                (Ok(_), _) if native => Outcome::Failed(format!(
+                    "conversion into {} failed with {}",
+                    column, reason
+                )),
+                (Ok(_), _) => Outcome::Unsupported,
+                (Err(ConversionError::NumberRange(_)), ConversionError::NumberRange(_)) => {
+                    Outcome::Rejected
+                }
+                // the driver does not support the rust type at all
+                (Err(ConversionError::NumberRange(_)), ConversionError::ValueType(_)) => {
+                    Outcome::Unsupported
+                }
+                (Err(ConversionError::NumberRange(_)), _) => Outcome::Failed(format!(
+                    "failed with {} instead of a range error",
+                    reason
+                )),
+                (Err(_), _) => Outcome::Rejected,
+            };
+        }
+    };
+    match new_value().into_typed::<Option<T>>() {
+        Ok(Some(ref optional)) if *optional == actual => {}
+        result => {
+            return Outcome::Failed(format!(
+                "converted into {:?}, but into {:?} as Option",
+                actual, result
+            ));
+        }
+    }
+    match expected {
+        Ok(ref expected) if *expected == actual => Outcome::Supported,
+        Ok(expected) => Outcome::Failed(format!(
+            "converted into {:?} instead of {:?}",
+            actual, expected
+        )),
+        Err(ConversionError::NumberRange(_)) => Outcome::Failed(format!(
+            "converted into {:?} instead of failing with a range error",
+            actual
+        )),
+        Err(_) => Outcome::Extended,
+    }
+}
+
+// The column of the rust type that matches the canonical value.
+fn native_column(value: &CanonicalValue) -> Option<&'static str> {
+    match *value {
+        CanonicalValue::Bool(_) => Some("bool"),
+        CanonicalValue::I64(_) => Some("i64"),
+        CanonicalValue::U64(_) => Some("u64"),
+        CanonicalValue::F64(_) => Some("f64"),
+        CanonicalValue::String(_) => Some("String"),
+        CanonicalValue::Bytes(_) => Some("bytes"),
+        _ => None,
+    }
+}
+
+// The conversion of the driver, which the deserialization bypasses
+// if the value provides its canonical representation.
+pub(crate) trait FromDbValue<V>: Sized {
+    fn from_db_value(value: V) -> Result<Self, ConversionError>;
+}
+
+macro_rules! impl_from_db_value {
+    ($($t:ty),*) => {
+        $(
+            impl<V: DbValue> FromDbValue<V> for $t {
+                fn from_db_value(value: V) -> Result<$t, ConversionError> {
+                    DbValueInto::<$t>::try_into(value)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_db_value!(bool, u8, u16, u32, u64, i8, i16, i32, i64, f32, f64, String);
+
+impl<V: DbValue> FromDbValue<V> for u128 {
+    fn from_db_value(value: V) -> Result<u128, ConversionError> {
+        value.try_into_u128()
+    }
+}
+
+impl<V: DbValue> FromDbValue<V> for i128 {
+    fn from_db_value(value: V) -> Result<i128, ConversionError> {
+        value.try_into_i128()
+    }
+}
+
+impl<V: DbValue> FromDbValue<V> for ByteBuf {
+    fn from_db_value(value: V) -> Result<ByteBuf, ConversionError> {
+        DbValueInto::<Vec<u8>>::try_into(value).map(ByteBuf)
+    }
+}
+
+// Borrowing strings is optional, but must give the content of the value.
+fn check_borrowed_str<V: DbValue>(new_value: &dyn Fn() -> V, expected: &CanonicalValue) -> Outcome {
+    match new_value().as_str() {
+        None => Outcome::Unsupported,
+        Some(s) => match *expected {
+            CanonicalValue::String(ref expected) if expected == s => Outcome::Supported,
+            _ => Outcome::Failed(format!("as_str() returns {:?} for {:?}", s, expected)),
+        },
+    }
+}
+
+fn check_null<V, T>(new_value: &dyn Fn() -> V) -> Outcome
+where
+    V: DbValue,
+    T: DeserializeOwned + fmt::Debug,
+{
+    match new_value().into_typed::<Option<T>>() {
+        Ok(None) => {}
+        result => return Outcome::Failed(format!("converted into {:?} as Option", result)),
+    }
+    match new_value().into_typed::<T>() {
+        Ok(value) => Outcome::Failed(format!("converted into {:?}", value)),
+        Err(_) => Outcome::Rejected,
+    }
+}
+
+// A byte buffer that is deserialized with deserialize_byte_buf(), unlike Vec<u8>.
+#[derive(Debug, PartialEq)]
+pub(crate) struct ByteBuf(pub(crate) Vec<u8>);
+
+impl FromCanonical for ByteBuf {
+    fn from_canonical(
+        value: CanonicalValue,
+        policy: ConversionPolicy,
+    ) -> Result<ByteBuf, ConversionError> {
+        Vec::<u8>::from_canonical(value, policy).map(ByteBuf)
+    }
+}
+
+impl serde::Serialize for ByteBuf {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for ByteBuf {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<ByteBuf, D::Error> {
+        deserializer.deserialize_byte_buf(ByteBufVisitor)
+    }
+}
+
+struct ByteBufVisitor;
+
+impl<'de> Visitor<'de> for ByteBufVisitor {
+    type Value = ByteBuf;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a byte buffer")
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<ByteBuf, E> {
+        Ok(ByteBuf(v.to_vec()))
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<ByteBuf, E> {
+        Ok(ByteBuf(v))
+    }
+}
@@ -0,0 +1,41 @@
+/// Defines which conversions between numbers of different kinds (integers, floating point
+/// numbers, decimals) and between numbers and strings are applied.
+///
+/// Each policy allows everything that the preceding ones allow.
+///
+/// In the deserialization, the policy is applied to all values for which the driver provides
+/// the canonical representation (see
+/// [`DbValue::canonical()`](../de/trait.DbValue.html#method.canonical));
+/// other values can only be converted with the default policy.
+/// The policy is set with
+/// [`DeserializationOptions::conversion_policy()`](../de/struct.DeserializationOptions.html#method.conversion_policy).
+/// In the serialization, the policy decides which fallbacks are tried if a
+/// [`DbvFactory`](../ser/trait.DbvFactory.html) rejects the type of a value;
+/// it is set with [`to_params_with()`](../ser/fn.to_params_with.html), while
+/// [`to_params()`](../ser/fn.to_params.html) applies the default policy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ConversionPolicy {
+    /// Integers are only converted into integers, floating point numbers only into
+    /// floating point numbers, and only if the value is represented exactly.
+    Strict,
+    /// Numbers are also converted between integers, floating point numbers, and decimals,
+    /// as long as the value fits into the target type without losing precision;
+    /// decimals are converted into the nearest floating point number.
+    ///
+    /// This is the default.
+    RangeChecked,
+    /// Fractions are rounded to the nearest integer, and a loss of precision
+    /// is accepted, e.g. when a large `i64` is converted into an `f64`.
+    /// Values that exceed the range of the target type are still rejected.
+    Lossy,
+    /// Strings are parsed into numbers and booleans.
+    ParseFromString,
+}
+
+// `#[derive(Default)]` with a `#[default]` variant would require rust 1.62
+#[allow(clippy::derivable_impls)]
+impl Default for ConversionPolicy {
+    fn default() -> ConversionPolicy {
+        ConversionPolicy::RangeChecked
+    }
+}
@@ -71,6 +71,28 @@
 //! let s: String = resultset.into_typed()?;
 //! ```
 //!
+//! ## Convert a resultset with at most one row into an Option:
+//!
+//! An empty resultset is converted into `None`, a resultset with a single row into `Some`;
+//! an empty resultset can also be converted into `()`.
+//!
+//! ```ignore
+//! let o: Option<MyStruct> = resultset.into_typed()?;
+//! ```
+//!
+//! ## Convert fields into enums:
+//!
+//! Fields can be deserialized into enums with unit variants.
+//! String values are matched against the variant names (`#[serde(rename)]` is honored),
+//! integer values (see `DbValue::is_integer()`) are interpreted as the variant index.
+//!
+//! ```ignore
+//! #[derive(Deserialize)]
+//! enum Status {Open, InProgress, Closed}
+//! ...
+//! let status: Status = resultset.into_typed()?;
+//! ```
+//!
 //! # Rows
 //!
 //! ## Convert rows into tuples or structs
@@ -91,6 +113,68 @@
 //! }
 //! ```
 //!
+//! or, without a loop in the application, with
+//!
+//! ```ignore
+//! for data in resultset.into_typed_iter::<MyStruct>() {
+//!     let data = data?;
+//! }
+//! ```
+//!
+//! ## Convert rows into nested structs
+//!
+//! Fields of a struct can be structs themselves; they are filled from the consecutive
+//! columns whose names consist of the field name, a nesting separator (`_` or `.`),
+//! and the field names of the nested struct:
+//!
+//! ```ignore
+//! #[derive(Deserialize)]
+//! struct Address {street: String, city: String}
+//! #[derive(Deserialize)]
+//! struct Customer {id: i32, address: Address}
+//! ...
+//! // select id, street as address_street, city as address_city from ...
+//! let customers: Vec<Customer> = resultset.into_typed()?;
+//! ```
+//!
+//! Rows can also be converted into tuples of structs, like `(User, Order)`;
+//! the columns of each struct are identified by a prefix, see
+//! [`DeserializationOptions::tuple_prefixes()`](struct.DeserializationOptions.html#method.tuple_prefixes).
+//!
+//! ## Convert rows into maps
+//!
+//! Rows can also be converted into maps, with the column names as keys:
+//!
+//! ```ignore
+//! let data: Vec<HashMap<String, Option<String>>> = resultset.into_typed()?;
+//! ```
+//!
+//! A resultset can be converted into a map with one entry per row:
+//! the first column provides the key, and the remaining columns provide the value,
+//! which is a plain value if the resultset has two columns,
+//! or a struct or a tuple if it has more columns:
+//!
+//! ```ignore
+//! let names: HashMap<i64, String> = resultset.into_typed()?;   // select id, name ...
+//! let customers: BTreeMap<i64, Customer> = resultset.into_typed()?;   // select id, name, city ...
+//! ```
+//!
+//! ## Convert a resultset column-wise
+//!
+//! For numeric processing, a resultset can also be converted into a struct with one `Vec`
+//! per column, using
+//! [`into_columns()`](trait.DeserializableResultset.html#method.into_columns);
+//! the struct fields are matched with the column names:
+//!
+//! ```ignore
+//! #[derive(Deserialize)]
+//! struct Prices {
+//!     day: Vec<NaiveDate>,
+//!     price: Vec<f64>,
+//! }
+//! let prices: Prices = resultset.into_columns()?;
+//! ```
+//!
 //! # Individual values
 //!
 //! When necessary, you can also convert individual values directly into an adequate rust type:
@@ -102,6 +186,16 @@
 //! }
 //! ```
 //!
+//! # Asynchronous resultsets
+//!
+//! With the feature `async`, drivers that fetch rows asynchronously can implement
+//! [`AsyncDeserializableResultset`](trait.AsyncDeserializableResultset.html),
+//! which provides a `Stream` of typed rows, and a future for the typed resultset:
+//!
+//! ```ignore
+//! let data: Vec<MyStruct> = resultset.into_typed().await?;
+//! ```
+//!
 //! # Note for implementors
 //!
 //! Implementing DB drivers need
@@ -110,6 +204,14 @@
 //! and [`DbValue`](trait.DbValue.html), which is a bit more effort
 //! (an example can be found in the tests of this crate), depending on the flexibility
 //! you want to offer.
+//! Drivers whose values borrow from their row buffers can additionally implement
+//! [`DbValue::as_str()`](trait.DbValue.html#method.as_str) and
+//! [`DbValue::as_bytes()`](trait.DbValue.html#method.as_bytes), which allows
+//! deserializing into `&str`, `&[u8]`, and `Cow<str>` without copying the data.
+//!
+//! For value enums whose variants map directly onto rust types, like
+//! `NullableShort(Option<i16>)`, the companion crate `serde_db_derive` generates the
+//! implementation of `DbValue` with `#[derive(DbValue)]`.
 //!
 //! We further recommend adding a method like `into_typed()` directly on the
 //! driver's class for resultsets with a plain delegation to the _provided_ method
@@ -119,17 +221,38 @@
 //! By this, the deserialization functionality of `serde_db` can be provided
 //! to the users of the DB driver without forcing them to import `serde_db`.
 
+#[cfg(feature = "async")]
+mod async_deserializable_resultset;
+mod buffered_row;
+mod canonical_db_value;
+mod columns_deserializer;
 mod conversion_error;
 mod db_value;
 mod deserializable_resultset;
 mod deserializable_row;
 mod deserialization_error;
+mod deserialization_options;
 mod field_deserializer;
 mod row_deserializer;
 mod rs_deserializer;
+mod typed_iter;
+#[cfg(feature = "async")]
+mod typed_stream;
 
+#[cfg(feature = "async")]
+pub use self::async_deserializable_resultset::{AsyncDeserializableResultset, IntoTyped, Next};
+pub use self::canonical_db_value::{CanonicalDbValue, CanonicalValue};
+#[cfg(feature = "conformance")]
+pub(crate) use self::canonical_db_value::FromCanonical;
 pub use self::conversion_error::ConversionError;
+pub use crate::conversion_policy::ConversionPolicy;
 pub use self::db_value::{DbValue, DbValueInto};
 pub use self::deserializable_resultset::DeserializableResultset;
 pub use self::deserializable_row::DeserializableRow;
 pub use self::deserialization_error::{DeserializationError, DeserializationResult};
+pub use self::deserialization_options::{
+    ColumnNameMatching, DeserializationOptions, RowCardinality,
+};
+pub use self::typed_iter::TypedIter;
+#[cfg(feature = "async")]
+pub use self::typed_stream::TypedStream;
@@ -0,0 +1,216 @@
+use futures_core::task::{Context, Poll};
+use futures_core::Future;
+use serde;
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+use std::pin::Pin;
+
+use crate::de::rs_deserializer::RsDeserializer;
+use crate::de::typed_stream::TypedStream;
+use crate::de::{
+    DeserializableResultset, DeserializableRow, DeserializationError, DeserializationOptions,
+    DeserializationResult,
+};
+
+/// Interface for a database resultset whose rows are fetched asynchronously.
+///
+/// This is the async counterpart of
+/// [`DeserializableResultset`](trait.DeserializableResultset.html).
+pub trait AsyncDeserializableResultset: Sized {
+    /// Error type of the database driver.
+    type E: From<DeserializationError> + Sized;
+    /// Concrete type for the DB row, which must implement DeserializabeRow.
+    type ROW: DeserializableRow;
+
+    /// Attempts to remove the next row and to return it;
+    /// returns `Poll::Ready(Ok(None))` if the result set is exhausted.
+    ///
+    /// If the next row is not yet available, `Poll::Pending` is returned,
+    /// and the waker of the context is woken when it becomes available.
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context,
+    ) -> Poll<DeserializationResult<Option<Self::ROW>>>;
+
+    /// Returns the number of fields in each (complete) row.
+    fn number_of_fields(&self) -> usize;
+
+    /// Returns the name of the column at the specified index.
+    fn fieldname(&self, field_idx: usize) -> Option<&String>;
+
+    /// A _provided method_ that returns a future for the next row.
+    fn next(&mut self) -> Next<'_, Self>
+    where
+        Self: Unpin,
+    {
+        Next { rs: self }
+    }
+
+    /// A _provided method_ that returns a stream which fetches the rows lazily
+    /// and converts each of them into the given rust type.
+    ///
+    /// ```ignore
+    /// let mut stream = resultset.into_typed_stream::<MyStruct>();
+    /// while let Some(my_struct) = stream.next().await {
+    ///     let my_struct = my_struct?;
+    ///     ...
+    /// }
+    /// ```
+    fn into_typed_stream<T>(self) -> TypedStream<Self, T>
+    where
+        T: serde::de::DeserializeOwned,
+        Self: Unpin,
+    {
+        self.into_typed_stream_with(&DeserializationOptions::default())
+    }
+
+    /// A _provided method_ that returns a stream which fetches the rows lazily
+    /// and converts each of them into the given rust type, using the given options.
+    fn into_typed_stream_with<T>(self, options: &DeserializationOptions) -> TypedStream<Self, T>
+    where
+        T: serde::de::DeserializeOwned,
+        Self: Unpin,
+    {
+        TypedStream::new(self, options.clone())
+    }
+
+    /// A _provided method_ that returns a future which fetches all rows and
+    /// translates the resultset into the given rust type, like
+    /// [`DeserializableResultset::into_typed()`](trait.DeserializableResultset.html#method.into_typed).
+    ///
+    /// ```ignore
+    /// let typed_result: Vec<MyStruct> = resultset.into_typed().await?;
+    /// ```
+    fn into_typed<T>(self) -> IntoTyped<Self, T>
+    where
+        T: serde::de::DeserializeOwned,
+        Self: Unpin,
+    {
+        self.into_typed_with(&DeserializationOptions::default())
+    }
+
+    /// A _provided method_ that returns a future which fetches all rows and
+    /// translates the resultset into the given rust type, using the given options.
+    fn into_typed_with<T>(self, options: &DeserializationOptions) -> IntoTyped<Self, T>
+    where
+        T: serde::de::DeserializeOwned,
+        Self: Unpin,
+    {
+        let fieldnames = (0..self.number_of_fields())
+            .map(|idx| self.fieldname(idx).cloned().unwrap_or_default())
+            .collect();
+        IntoTyped {
+            rs: self,
+            rows: Some(FetchedRows {
+                rows: VecDeque::new(),
+                fieldnames,
+            }),
+            options: options.clone(),
+            target: PhantomData,
+        }
+    }
+}
+
+/// Future for the next row of an
+/// [`AsyncDeserializableResultset`](trait.AsyncDeserializableResultset.html).
+pub struct Next<'a, RS> {
+    rs: &'a mut RS,
+}
+
+impl<'a, RS> Future for Next<'a, RS>
+where
+    RS: AsyncDeserializableResultset + Unpin,
+{
+    type Output = DeserializationResult<Option<RS::ROW>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        Pin::new(&mut *self.rs).poll_next(cx)
+    }
+}
+
+/// Future for the typed content of an
+/// [`AsyncDeserializableResultset`](trait.AsyncDeserializableResultset.html).
+///
+/// Like the futures of `async fn`, the future panics if it is polled again
+/// after it has completed.
+pub struct IntoTyped<RS: AsyncDeserializableResultset, T> {
+    rs: RS,
+    // is None after completion
+    rows: Option<FetchedRows<RS::ROW>>,
+    options: DeserializationOptions,
+    target: PhantomData<fn() -> T>,
+}
+
+// The fetched rows are never pinned.
+impl<RS: AsyncDeserializableResultset + Unpin, T> Unpin for IntoTyped<RS, T> {}
+
+impl<RS, T> Future for IntoTyped<RS, T>
+where
+    RS: AsyncDeserializableResultset + Unpin,
+    T: serde::de::DeserializeOwned,
+{
+    type Output = Result<T, RS::E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let fetched = match this.rows {
+            Some(ref mut fetched) => fetched,
+            None => panic!("IntoTyped polled after completion"),
+        };
+        loop {
+            match Pin::new(&mut this.rs).poll_next(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Err(e)) => {
+                    this.rows = None;
+                    return Poll::Ready(Err(e.into()));
+                }
+                Poll::Ready(Ok(Some(row))) => fetched.rows.push_back(row),
+                Poll::Ready(Ok(None)) => break,
+            }
+        }
+        match this.rows.take() {
+            Some(fetched) => Poll::Ready(fetched.into_typed(&this.options)),
+            None => unreachable!("the rows are only taken here"),
+        }
+    }
+}
+
+// The completely fetched rows of an AsyncDeserializableResultset,
+// which are deserialized with the synchronous machinery.
+struct FetchedRows<ROW> {
+    rows: VecDeque<ROW>,
+    fieldnames: Vec<String>,
+}
+
+impl<ROW: DeserializableRow> FetchedRows<ROW> {
+    fn into_typed<T, E>(self, options: &DeserializationOptions) -> Result<T, E>
+    where
+        T: serde::de::DeserializeOwned,
+        E: From<DeserializationError>,
+    {
+        Ok(serde::de::Deserialize::deserialize(
+            &mut RsDeserializer::try_new(self, options)?,
+        )?)
+    }
+}
+
+impl<ROW: DeserializableRow> DeserializableResultset for FetchedRows<ROW> {
+    type E = DeserializationError;
+    type ROW = ROW;
+
+    fn has_multiple_rows(&mut self) -> DeserializationResult<bool> {
+        Ok(self.rows.len() > 1)
+    }
+
+    fn next(&mut self) -> DeserializationResult<Option<ROW>> {
+        Ok(self.rows.pop_front())
+    }
+
+    fn number_of_fields(&self) -> usize {
+        self.fieldnames.len()
+    }
+
+    fn fieldname(&self, field_idx: usize) -> Option<&String> {
+        self.fieldnames.get(field_idx)
+    }
+}
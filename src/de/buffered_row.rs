@@ -0,0 +1,58 @@
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use crate::de::{DbValue, DeserializableRow, DeserializationError};
+
+// A row whose values are read completely from the database row,
+// so that they can be inspected before the row is deserialized.
+pub struct BufferedRow<V> {
+    values: VecDeque<V>,
+    fieldnames: Rc<Vec<String>>,
+}
+
+impl<V: DbValue> BufferedRow<V> {
+    pub fn new<ROW>(mut row: ROW, fieldnames: Rc<Vec<String>>) -> BufferedRow<V>
+    where
+        ROW: DeserializableRow<V = V>,
+    {
+        let mut values = VecDeque::with_capacity(row.len());
+        while let Some(value) = row.next() {
+            values.push_back(value);
+        }
+        BufferedRow { values, fieldnames }
+    }
+
+    // A row of the given trailing values; the field names include those of the values
+    // that were consumed before, so that the field indices are kept.
+    pub fn from_values(values: VecDeque<V>, fieldnames: Rc<Vec<String>>) -> BufferedRow<V> {
+        BufferedRow { values, fieldnames }
+    }
+
+    // Returns the value at the specified index, if it was not yet consumed;
+    // a row with more values than field names has no valid index.
+    pub fn value(&self, field_idx: usize) -> Option<&V> {
+        let consumed = self.fieldnames.len().checked_sub(self.values.len())?;
+        self.values.get(field_idx.checked_sub(consumed)?)
+    }
+}
+
+impl<V: DbValue> DeserializableRow for BufferedRow<V> {
+    type E = DeserializationError;
+    type V = V;
+
+    fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    fn next(&mut self) -> Option<V> {
+        self.values.pop_front()
+    }
+
+    fn number_of_fields(&self) -> usize {
+        self.fieldnames.len()
+    }
+
+    fn fieldname(&self, field_idx: usize) -> Option<&String> {
+        self.fieldnames.get(field_idx)
+    }
+}
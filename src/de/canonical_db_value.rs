@@ -0,0 +1,337 @@
+use std::convert::TryFrom;
+use std::f32;
+
+use crate::de::{ConversionError, ConversionPolicy, DbValueInto};
+
+/// The canonical representation of a database value, see
+/// [`CanonicalDbValue`](trait.CanonicalDbValue.html).
+#[derive(Clone, Debug, PartialEq)]
+pub enum CanonicalValue {
+    /// The NULL value.
+    Null,
+    /// A boolean value.
+    Bool(bool),
+    /// A signed integer value.
+    I64(i64),
+    /// An unsigned integer value.
+    U64(u64),
+    /// A floating point value.
+    F64(f64),
+    /// A decimal value in its string representation, like `-1234.50`.
+    Decimal(String),
+    /// A string value.
+    String(String),
+    /// A binary value.
+    Bytes(Vec<u8>),
+}
+
+/// A helper for implementing `DbValue`.
+///
+/// Rather than implementing `DbValueInto<T>` for all supported rust types, a driver can
+/// implement this trait and only provide the canonical representation of its values.
+/// `serde_db` derives all `DbValueInto` conversions from it; the numeric conversions follow
+/// the default [`ConversionPolicy`](enum.ConversionPolicy.html), and fail with
+/// `ConversionError::NumberRange` if the value does not fit into the target type.
+///
+/// To let `serde_db` apply the conversion policy of the deserialization, the driver also needs
+/// to implement [`DbValue::canonical()`](trait.DbValue.html#method.canonical).
+///
+/// Note that `serde_db` implements `DbValueInto<T>` for all types that implement this trait;
+/// a driver can therefore not implement a single `DbValueInto<T>` on its own.
+/// Drivers that need a deviating conversion for some rust type must implement all
+/// `DbValueInto<T>` themselves, rather than this trait.
+///
+/// ```ignore
+/// impl CanonicalDbValue for MyDbValue {
+///     fn into_canonical(self) -> Result<CanonicalValue, ConversionError> {
+///         Ok(match self {
+///             MyDbValue::NULL => CanonicalValue::Null,
+///             MyDbValue::TINYINT(u) => CanonicalValue::U64(u64::from(u)),
+///             MyDbValue::INT(i) => CanonicalValue::I64(i64::from(i)),
+///             MyDbValue::DOUBLE(f) => CanonicalValue::F64(f),
+///             MyDbValue::DECIMAL(d) => CanonicalValue::Decimal(d.to_string()),
+///             MyDbValue::STRING(s) => CanonicalValue::String(s),
+///             MyDbValue::BLOB(b) => CanonicalValue::Bytes(b),
+///         })
+///     }
+/// }
+///
+/// impl DbValue for MyDbValue {
+///     fn is_null(&self) -> bool {
+///         match *self {
+///             MyDbValue::NULL => true,
+///             _ => false,
+///         }
+///     }
+///
+///     fn is_integer(&self) -> bool {
+///         match *self {
+///             MyDbValue::TINYINT(_) | MyDbValue::INT(_) => true,
+///             _ => false,
+///         }
+///     }
+/// }
+/// ```
+pub trait CanonicalDbValue: Sized {
+    /// Converts the value into its canonical representation.
+    fn into_canonical(self) -> Result<CanonicalValue, ConversionError>;
+}
+
+// Conversion of canonical values into rust types, according to a conversion policy.
+pub(crate) trait FromCanonical: Sized {
+    fn from_canonical(
+        value: CanonicalValue,
+        policy: ConversionPolicy,
+    ) -> Result<Self, ConversionError>;
+}
+
+fn value_type_error(value: &CanonicalValue, target: &str) -> ConversionError {
+    ConversionError::ValueType(format!("{:?} cannot be converted into {}", value, target))
+}
+
+fn range_error<T: ToString>(value: T, target: &str) -> ConversionError {
+    ConversionError::NumberRange(format!("{} does not fit into {}", value.to_string(), target))
+}
+
+fn precision_error<T: ToString>(value: T, target: &str) -> ConversionError {
+    ConversionError::NumberRange(format!(
+        "{} cannot be represented exactly in {}",
+        value.to_string(),
+        target
+    ))
+}
+
+// Parses integers, and decimals without fractional digits, like "12.00".
+fn parse_integer(s: &str) -> Option<i128> {
+    let int_part = match s.find('.') {
+        Some(pos) if s[pos + 1..].chars().all(|c| c == '0') => &s[..pos],
+        Some(_) => return None,
+        None => s,
+    };
+    int_part.parse().ok()
+}
+
+fn float_into_i128(
+    f: f64,
+    policy: ConversionPolicy,
+    target: &str,
+) -> Result<i128, ConversionError> {
+    let f = if !f.is_finite() {
+        return Err(range_error(f, target));
+    } else if f.fract() == 0.0 {
+        f
+    } else if policy >= ConversionPolicy::Lossy {
+        f.round()
+    } else {
+        return Err(ConversionError::ValueType(format!(
+            "{} cannot be converted into {} without rounding",
+            f, target
+        )));
+    };
+    if f.abs() < 1e38 {
+        Ok(f as i128)
+    } else {
+        Err(range_error(f, target))
+    }
+}
+
+fn parse_into_i128(
+    value: &CanonicalValue,
+    s: &str,
+    policy: ConversionPolicy,
+    target: &str,
+) -> Result<i128, ConversionError> {
+    let s = s.trim();
+    if let Some(i) = parse_integer(s) {
+        return Ok(i);
+    }
+    match s.parse() {
+        Ok(f) => float_into_i128(f, policy, target),
+        Err(_) => Err(value_type_error(value, target)),
+    }
+}
+
+fn into_i128(
+    value: CanonicalValue,
+    policy: ConversionPolicy,
+    target: &str,
+) -> Result<i128, ConversionError> {
+    match value {
+        CanonicalValue::I64(i) => Ok(i128::from(i)),
+        CanonicalValue::U64(u) => Ok(i128::from(u)),
+        CanonicalValue::Bool(b) if policy >= ConversionPolicy::RangeChecked => Ok(i128::from(b)),
+        CanonicalValue::F64(f) if policy >= ConversionPolicy::RangeChecked => {
+            float_into_i128(f, policy, target)
+        }
+        CanonicalValue::Decimal(ref s) if policy >= ConversionPolicy::RangeChecked => {
+            parse_into_i128(&value, s, policy, target)
+        }
+        CanonicalValue::String(ref s) if policy >= ConversionPolicy::ParseFromString => {
+            parse_into_i128(&value, s, policy, target)
+        }
+        value => Err(value_type_error(&value, target)),
+    }
+}
+
+// Integers are only converted without the lossy policies if they have
+// at most `mantissa_digits` significant bits.
+fn into_float(
+    value: CanonicalValue,
+    policy: ConversionPolicy,
+    target: &str,
+    mantissa_digits: u32,
+) -> Result<f64, ConversionError> {
+    let int_into_float = |i: i128| {
+        let limit = 1_i128 << mantissa_digits;
+        if policy >= ConversionPolicy::Lossy || (-limit..=limit).contains(&i) {
+            Ok(i as f64)
+        } else {
+            Err(precision_error(i, target))
+        }
+    };
+    let parse_float = |value: &CanonicalValue, s: &str| match s.trim().parse() {
+        Ok(f) => Ok(f),
+        Err(_) => Err(value_type_error(value, target)),
+    };
+    match value {
+        CanonicalValue::F64(f) => Ok(f),
+        CanonicalValue::I64(i) if policy >= ConversionPolicy::RangeChecked => {
+            int_into_float(i128::from(i))
+        }
+        CanonicalValue::U64(u) if policy >= ConversionPolicy::RangeChecked => {
+            int_into_float(i128::from(u))
+        }
+        CanonicalValue::Decimal(ref s) if policy >= ConversionPolicy::RangeChecked => {
+            parse_float(&value, s)
+        }
+        CanonicalValue::String(ref s) if policy >= ConversionPolicy::ParseFromString => {
+            parse_float(&value, s)
+        }
+        value => Err(value_type_error(&value, target)),
+    }
+}
+
+macro_rules! impl_integer_from_canonical {
+    ($($t:ty),*) => {
+        $(
+            impl FromCanonical for $t {
+                fn from_canonical(
+                    value: CanonicalValue,
+                    policy: ConversionPolicy,
+                ) -> Result<$t, ConversionError> {
+                    let target = stringify!($t);
+                    let i = into_i128(value, policy, target)?;
+                    <$t>::try_from(i).map_err(|_| range_error(i, target))
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_from_canonical!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+impl FromCanonical for f64 {
+    fn from_canonical(
+        value: CanonicalValue,
+        policy: ConversionPolicy,
+    ) -> Result<f64, ConversionError> {
+        into_float(value, policy, "f64", 53)
+    }
+}
+
+impl FromCanonical for f32 {
+    fn from_canonical(
+        value: CanonicalValue,
+        policy: ConversionPolicy,
+    ) -> Result<f32, ConversionError> {
+        let from_float = matches!(value, CanonicalValue::F64(_));
+        let f = into_float(value, policy, "f32", 24)?;
+        if f.is_finite() && f.abs() > f64::from(f32::MAX) {
+            Err(range_error(f, "f32"))
+        } else if from_float
+            && f.is_finite()
+            && policy < ConversionPolicy::Lossy
+            && f64::from(f as f32) != f
+        {
+            Err(precision_error(f, "f32"))
+        } else {
+            Ok(f as f32)
+        }
+    }
+}
+
+impl FromCanonical for bool {
+    fn from_canonical(
+        value: CanonicalValue,
+        policy: ConversionPolicy,
+    ) -> Result<bool, ConversionError> {
+        match value {
+            CanonicalValue::Bool(b) => Ok(b),
+            CanonicalValue::I64(0) | CanonicalValue::U64(0)
+                if policy >= ConversionPolicy::RangeChecked =>
+            {
+                Ok(false)
+            }
+            CanonicalValue::I64(1) | CanonicalValue::U64(1)
+                if policy >= ConversionPolicy::RangeChecked =>
+            {
+                Ok(true)
+            }
+            CanonicalValue::String(ref s) if policy >= ConversionPolicy::ParseFromString => {
+                match s.trim().parse() {
+                    Ok(b) => Ok(b),
+                    Err(_) => Err(value_type_error(&value, "bool")),
+                }
+            }
+            value => Err(value_type_error(&value, "bool")),
+        }
+    }
+}
+
+impl FromCanonical for String {
+    fn from_canonical(
+        value: CanonicalValue,
+        _policy: ConversionPolicy,
+    ) -> Result<String, ConversionError> {
+        match value {
+            CanonicalValue::Bool(b) => Ok(b.to_string()),
+            CanonicalValue::I64(i) => Ok(i.to_string()),
+            CanonicalValue::U64(u) => Ok(u.to_string()),
+            CanonicalValue::F64(f) => Ok(f.to_string()),
+            CanonicalValue::Decimal(s) | CanonicalValue::String(s) => Ok(s),
+            CanonicalValue::Bytes(b) => String::from_utf8(b).map_err(|e| {
+                ConversionError::ValueType(format!("bytes are no valid UTF-8: {}", e))
+            }),
+            value => Err(value_type_error(&value, "String")),
+        }
+    }
+}
+
+impl FromCanonical for Vec<u8> {
+    fn from_canonical(
+        value: CanonicalValue,
+        _policy: ConversionPolicy,
+    ) -> Result<Vec<u8>, ConversionError> {
+        match value {
+            CanonicalValue::Bytes(b) => Ok(b),
+            CanonicalValue::String(s) => Ok(s.into_bytes()),
+            value => Err(value_type_error(&value, "Vec<u8>")),
+        }
+    }
+}
+
+// All conversions of values that implement CanonicalDbValue, into numbers, bool, String,
+// and Vec<u8>, are derived from the canonical value, with the default policy.
+macro_rules! impl_db_value_into {
+    ($($t:ty),*) => {
+        $(
+            impl<V: CanonicalDbValue> DbValueInto<$t> for V {
+                fn try_into(self) -> Result<$t, ConversionError> {
+                    <$t>::from_canonical(self.into_canonical()?, ConversionPolicy::default())
+                }
+            }
+        )*
+    };
+}
+
+impl_db_value_into!(bool, u8, u16, u32, u64, i8, i16, i32, i64, f32, f64, String, Vec<u8>);
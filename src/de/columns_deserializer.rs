@@ -0,0 +1,230 @@
+use log::{debug, trace};
+use serde;
+use serde::de::IntoDeserializer;
+use serde::forward_to_deserialize_any;
+use std::vec;
+
+use crate::de::field_deserializer::FieldDeserializer;
+use crate::de::{
+    ConversionPolicy, DbValue, DeserializationError, DeserializationOptions, DeserializationResult,
+};
+
+// Deserialize the columns of a completely fetched resultset into a struct or a map
+// whose fields are sequences, like `Vec<T>`, one per column.
+pub struct ColumnsDeserializer<'o, DBV> {
+    columns: Vec<(String, Vec<DBV>)>,
+    options: &'o DeserializationOptions,
+}
+
+impl<'o, DBV: DbValue> ColumnsDeserializer<'o, DBV> {
+    pub fn new(
+        columns: Vec<(String, Vec<DBV>)>,
+        options: &'o DeserializationOptions,
+    ) -> ColumnsDeserializer<'o, DBV> {
+        trace!("ColumnsDeserializer::new()");
+        ColumnsDeserializer { columns, options }
+    }
+}
+
+impl<'de, 'o, DBV: DbValue + 'de> serde::Deserializer<'de> for ColumnsDeserializer<'o, DBV> {
+    type Error = DeserializationError;
+
+    fn deserialize_any<V>(self, _visitor: V) -> DeserializationResult<V::Value>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        Err(DeserializationError::Usage(
+            "the columns of a resultset can only be converted into a struct or a map".to_string(),
+        ))
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> DeserializationResult<V::Value>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        trace!("ColumnsDeserializer::deserialize_struct()");
+        visitor.visit_map(ColumnsMapVisitor::new(self, fields, false))
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> DeserializationResult<V::Value>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        trace!("ColumnsDeserializer::deserialize_map()");
+        visitor.visit_map(ColumnsMapVisitor::new(self, &[], true))
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string bytes byte_buf option
+        unit unit_struct newtype_struct seq tuple tuple_struct enum identifier ignored_any
+    }
+}
+
+// Provides the columns as entries; the keys are the field names (or, for maps,
+// the column names), the values are the complete columns.
+struct ColumnsMapVisitor<'o, DBV> {
+    columns: vec::IntoIter<(String, Vec<DBV>)>,
+    options: &'o DeserializationOptions,
+    fields: &'static [&'static str],
+    is_map: bool,
+    col_idx: usize,
+    current: Option<(usize, String, Vec<DBV>)>,
+    ignored_columns: Vec<String>,
+}
+
+impl<'o, DBV> ColumnsMapVisitor<'o, DBV> {
+    fn new(
+        de: ColumnsDeserializer<'o, DBV>,
+        fields: &'static [&'static str],
+        is_map: bool,
+    ) -> ColumnsMapVisitor<'o, DBV> {
+        trace!("ColumnsMapVisitor::new()");
+        ColumnsMapVisitor {
+            columns: de.columns.into_iter(),
+            options: de.options,
+            fields,
+            is_map,
+            col_idx: 0,
+            current: None,
+            ignored_columns: Vec::new(),
+        }
+    }
+
+    fn report_ignored_columns(&self) {
+        if !self.ignored_columns.is_empty() {
+            debug!("Ignored columns without target field: {:?}", self.ignored_columns);
+            if let Some(ref callback) = self.options.ignored_columns_callback {
+                callback(&self.ignored_columns);
+            }
+        }
+    }
+}
+
+impl<'de, 'o, DBV: DbValue + 'de> serde::de::MapAccess<'de> for ColumnsMapVisitor<'o, DBV> {
+    type Error = DeserializationError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: serde::de::DeserializeSeed<'de>,
+    {
+        trace!("ColumnsMapVisitor.next_key_seed()");
+        let matching = &self.options.column_name_matching;
+        for (name, values) in self.columns.by_ref() {
+            let col_idx = self.col_idx;
+            self.col_idx += 1;
+            let key = if self.is_map {
+                Some(name.clone())
+            } else {
+                let normalized = matching.normalize(&name);
+                self.fields
+                    .iter()
+                    .find(|field| matching.eq(field, &normalized))
+                    .map(|field| (*field).to_string())
+            };
+            match key {
+                Some(key) => {
+                    self.current = Some((col_idx, name, values));
+                    return seed
+                        .deserialize(IntoDeserializer::<DeserializationError>::into_deserializer(
+                            key,
+                        ))
+                        .map(Some);
+                }
+                None if self.options.ignore_unknown_columns => self.ignored_columns.push(name),
+                None => return Err(DeserializationError::UnknownField(name)),
+            }
+        }
+        self.report_ignored_columns();
+        Ok(None)
+    }
+
+    fn next_value_seed<VS>(&mut self, seed: VS) -> Result<VS::Value, Self::Error>
+    where
+        VS: serde::de::DeserializeSeed<'de>,
+    {
+        trace!("ColumnsMapVisitor.next_value_seed()");
+        match self.current.take() {
+            Some((col_idx, name, values)) => seed
+                .deserialize(ColumnDeserializer {
+                    values,
+                    policy: self.options.conversion_policy,
+                })
+                .map_err(|e| e.with_column(col_idx, Some(&name))),
+            None => Err(DeserializationError::Usage(
+                "next_value_seed() without key".to_string(),
+            )),
+        }
+    }
+}
+
+// Deserialize the values of a single column into a sequence.
+struct ColumnDeserializer<DBV> {
+    values: Vec<DBV>,
+    policy: ConversionPolicy,
+}
+
+impl<'de, DBV: DbValue + 'de> serde::Deserializer<'de> for ColumnDeserializer<DBV> {
+    type Error = DeserializationError;
+
+    fn deserialize_any<V>(self, visitor: V) -> DeserializationResult<V::Value>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        trace!("ColumnDeserializer::deserialize_any()");
+        visitor.visit_seq(ColumnSeqVisitor {
+            values: self.values.into_iter(),
+            row_idx: 0,
+            policy: self.policy,
+        })
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> DeserializationResult<V::Value>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        trace!("ColumnDeserializer::deserialize_option()");
+        visitor.visit_some(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string bytes byte_buf
+        unit unit_struct newtype_struct seq tuple tuple_struct map struct enum identifier
+        ignored_any
+    }
+}
+
+struct ColumnSeqVisitor<DBV> {
+    values: vec::IntoIter<DBV>,
+    row_idx: usize,
+    policy: ConversionPolicy,
+}
+
+impl<'de, DBV: DbValue + 'de> serde::de::SeqAccess<'de> for ColumnSeqVisitor<DBV> {
+    type Error = DeserializationError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: serde::de::DeserializeSeed<'de>,
+    {
+        trace!("ColumnSeqVisitor.next_element_seed()");
+        match self.values.next() {
+            None => Ok(None),
+            Some(value) => {
+                let row_idx = self.row_idx;
+                self.row_idx += 1;
+                seed.deserialize(FieldDeserializer::new(value).with_policy(self.policy))
+                    .map(Some)
+                    .map_err(|e| e.with_row(row_idx))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.values.len())
+    }
+}
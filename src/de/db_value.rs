@@ -2,7 +2,7 @@ use serde;
 use std::{i16, i32, i8, u16, u32, u8};
 use std::marker::Sized;
 
-use crate::de::{ConversionError, DeserializationError};
+use crate::de::{CanonicalValue, ConversionError, DeserializationError};
 use crate::de::field_deserializer::FieldDeserializer;
 
 /// Defines into which rust types we support deserialization of fields.
@@ -24,10 +24,114 @@ pub trait DbValue
     /// Returns true if this is a NULL value.
     fn is_null(&self) -> bool;
 
+    /// Returns true if the value has an integer type.
+    ///
+    /// Integer values are deserialized into enums by variant index, using `DbValueInto<u32>`,
+    /// all other values by variant name, using `DbValueInto<String>`.
+    /// Since the value is consumed by the conversion, the deserialization cannot try one
+    /// after the other, so the driver must tell integer values apart, e.g. with
+    ///
+    /// ```ignore
+    /// fn is_integer(&self) -> bool {
+    ///     match *self {
+    ///         MyDbValue::TINYINT(_) | MyDbValue::SMALLINT(_) | MyDbValue::INT(_) => true,
+    ///         _ => false,
+    ///     }
+    /// }
+    /// ```
+    fn is_integer(&self) -> bool;
+
+    /// Converts the value into an i128.
+    ///
+    /// The default implementation uses `DbValueInto<i64>`; drivers that support
+    /// database types with a larger range, like `DECIMAL(38,0)`, should override it.
+    fn try_into_i128(self) -> Result<i128, ConversionError> {
+        DbValueInto::<i64>::try_into(self).map(i128::from)
+    }
+
+    /// Converts the value into an u128.
+    ///
+    /// The default implementation uses `DbValueInto<u64>`; drivers that support
+    /// database types with a larger range, like `DECIMAL(38,0)`, should override it.
+    fn try_into_u128(self) -> Result<u128, ConversionError> {
+        DbValueInto::<u64>::try_into(self).map(u128::from)
+    }
+
+    /// Returns the value as a string slice that is borrowed from a buffer
+    /// which outlives the deserialization, or `None` (which is the default).
+    ///
+    /// Drivers whose values borrow from their row buffers, like `MyDbValue<'buf>`,
+    /// can implement this method to avoid an allocation per string value if the target
+    /// is a `&str` or a `Cow<str>` (with `#[serde(borrow)]`):
+    ///
+    /// ```ignore
+    /// impl<'buf> DbValue for MyDbValue<'buf> {
+    ///     fn as_str<'de>(&self) -> Option<&'de str>
+    ///     where
+    ///         Self: 'de,
+    ///     {
+    ///         match *self {
+    ///             MyDbValue::STRING(s) => Some(s),
+    ///             _ => None,
+    ///         }
+    ///     }
+    ///     ...
+    /// }
+    /// ```
+    fn as_str<'de>(&self) -> Option<&'de str>
+    where
+        Self: 'de,
+    {
+        None
+    }
+
+    /// Returns the value as a byte slice that is borrowed from a buffer
+    /// which outlives the deserialization, or `None` (which is the default).
+    ///
+    /// See `as_str()`; this allows deserializing into `&[u8]` without an allocation.
+    fn as_bytes<'de>(&self) -> Option<&'de [u8]>
+    where
+        Self: 'de,
+    {
+        None
+    }
+
+    /// Returns the canonical representation of the value, or `None` (which is the default).
+    ///
+    /// The numeric conversions of the deserialization follow the
+    /// [`ConversionPolicy`](enum.ConversionPolicy.html) of the `DeserializationOptions`
+    /// only for values that provide their canonical representation; for all other values,
+    /// the `DbValueInto` implementations decide which conversions are possible.
+    ///
+    /// The method is called for each value that is converted into a number, a bool, or an enum,
+    /// and the canonical representation is built from a reference, so strings and byte arrays
+    /// would have to be copied. Drivers should thus only copy what is needed:
+    ///
+    /// ```ignore
+    /// fn canonical(&self) -> Option<CanonicalValue> {
+    ///     match *self {
+    ///         MyDbValue::NULL => Some(CanonicalValue::Null),
+    ///         MyDbValue::INT(i) => Some(CanonicalValue::I64(i64::from(i))),
+    ///         MyDbValue::DOUBLE(f) => Some(CanonicalValue::F64(f)),
+    ///         MyDbValue::DECIMAL(ref d) => Some(CanonicalValue::Decimal(d.to_string())),
+    ///         MyDbValue::STRING(ref s) => Some(CanonicalValue::String(s.clone())),
+    ///         MyDbValue::BLOB(_) => None,
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// Drivers whose values implement `Clone` and
+    /// [`CanonicalDbValue`](trait.CanonicalDbValue.html) can also use
+    /// `self.clone().into_canonical().ok()`, which however copies every value completely.
+    fn canonical(&self) -> Option<CanonicalValue> {
+        None
+    }
+
     /// Converts the DbValue into a plain rust value.
     fn into_typed<'de, T>(self) -> Result<T, DeserializationError>
     where
         T: serde::de::Deserialize<'de>,
+        Self: 'de,
     {
         Ok(serde::de::Deserialize::deserialize(FieldDeserializer::new(self))?)
     }
@@ -40,6 +144,9 @@ pub trait DbValue
 /// supporting every call as long as the concrete value can be converted. For the numeric
 /// types this requires quite some lines of code, but the effort pays off: it makes the usage of
 /// `serde_db` much more user-friendly.
+/// Alternatively, drivers can implement
+/// [`CanonicalDbValue`](trait.CanonicalDbValue.html), from which `serde_db` derives
+/// all implementations of `DbValueInto`.
 ///
 /// Example:
 ///
@@ -2,7 +2,10 @@ use serde;
 use std::marker::Sized;
 
 use crate::de::rs_deserializer::RsDeserializer;
-use crate::de::{DeserializableRow, DeserializationError, DeserializationResult};
+use crate::de::typed_iter::TypedIter;
+use crate::de::{
+    DeserializableRow, DeserializationError, DeserializationOptions, DeserializationResult,
+};
 
 /// Interface for a database resultset to support deserialization.
 pub trait DeserializableResultset: Sized {
@@ -36,13 +39,147 @@ pub trait DeserializableResultset: Sized {
     /// }
     /// let typed_result: Vec<MyStruct> = resultset.into_typed()?;
     /// ```
+    ///
+    /// A map is always filled from the rows, with the first column as key, and the remaining
+    /// columns as value, also if the resultset has a single row. To get a map from the column
+    /// names to the values of a single row, convert the row, rather than the resultset.
     fn into_typed<'de, T>(self) -> Result<T, Self::E>
     where
         T: serde::de::Deserialize<'de>,
+        <Self::ROW as DeserializableRow>::V: 'de,
+        Self: Sized,
+    {
+        self.into_typed_with(&DeserializationOptions::default())
+    }
+
+    /// A _provided method_ that translates a resultset into a given rust type
+    /// that implements serde::de::Deserialize, using the given options.
+    fn into_typed_with<'de, T>(self, options: &DeserializationOptions) -> Result<T, Self::E>
+    where
+        T: serde::de::Deserialize<'de>,
+        <Self::ROW as DeserializableRow>::V: 'de,
         Self: Sized,
     {
         Ok(serde::de::Deserialize::deserialize(
-            &mut RsDeserializer::try_new(self)?,
+            &mut RsDeserializer::try_new(self, options)?,
         )?)
     }
+
+    /// A _provided method_ that returns an iterator which fetches the rows lazily
+    /// and converts each of them into the given rust type.
+    ///
+    /// This allows processing large resultsets with constant memory consumption:
+    ///
+    /// ```ignore
+    /// for my_struct in resultset.into_typed_iter::<MyStruct>() {
+    ///     let my_struct = my_struct?;
+    ///     ...
+    /// }
+    /// ```
+    fn into_typed_iter<T>(self) -> TypedIter<Self, T>
+    where
+        T: serde::de::DeserializeOwned,
+        Self: Sized,
+    {
+        TypedIter::new(self, DeserializationOptions::default())
+    }
+
+    /// A _provided method_ that returns an iterator which fetches the rows lazily
+    /// and converts each of them into the given rust type, using the given options.
+    fn into_typed_iter_with<T>(self, options: &DeserializationOptions) -> TypedIter<Self, T>
+    where
+        T: serde::de::DeserializeOwned,
+        Self: Sized,
+    {
+        TypedIter::new(self, options.clone())
+    }
+
+    /// A _provided method_ that groups consecutive rows with equal values in the
+    /// key columns, and translates each group into an instance of the given rust type.
+    ///
+    /// This allows converting the resultset of a one-to-many join into parent structs
+    /// with a `Vec` of child structs; the child structs are filled, from each row of the group,
+    /// from the columns whose names have the name of the `Vec` field as prefix:
+    ///
+    /// ```ignore
+    /// #[derive(Deserialize)]
+    /// struct Item {
+    ///     pos: i32,
+    ///     article: String,
+    /// }
+    /// #[derive(Deserialize)]
+    /// struct Order {
+    ///     id: i32,
+    ///     customer: String,
+    ///     items: Vec<Item>,
+    /// }
+    /// // select o.id, o.customer, i.pos as items_pos, i.article as items_article
+    /// //   from orders o join items i on ... order by o.id
+    /// let orders: Vec<Order> = resultset.into_grouped(&["id"])?;
+    /// ```
+    fn into_grouped<'de, T>(self, key_columns: &[&str]) -> Result<Vec<T>, Self::E>
+    where
+        T: serde::de::Deserialize<'de>,
+        <Self::ROW as DeserializableRow>::V: 'de,
+        <Self::ROW as DeserializableRow>::V: PartialEq,
+        Self: Sized,
+    {
+        self.into_grouped_with(key_columns, &DeserializationOptions::default())
+    }
+
+    /// A _provided method_ that groups consecutive rows with equal values in the
+    /// key columns, and translates each group into an instance of the given rust type,
+    /// using the given options.
+    ///
+    /// The key columns are matched with the column names like field names,
+    /// see `DeserializationOptions::column_name_matching()`.
+    fn into_grouped_with<'de, T>(
+        self,
+        key_columns: &[&str],
+        options: &DeserializationOptions,
+    ) -> Result<Vec<T>, Self::E>
+    where
+        T: serde::de::Deserialize<'de>,
+        <Self::ROW as DeserializableRow>::V: 'de,
+        <Self::ROW as DeserializableRow>::V: PartialEq,
+        Self: Sized,
+    {
+        Ok(RsDeserializer::try_new(self, options)?.into_groups(key_columns)?)
+    }
+
+    /// A _provided method_ that translates the resultset column-wise into a struct
+    /// whose fields are `Vec`s, one per column, or into a map from the column names
+    /// to such `Vec`s.
+    ///
+    /// The struct fields are matched with the column names; this layout avoids
+    /// transposing the data for numeric processing:
+    ///
+    /// ```ignore
+    /// #[derive(Deserialize)]
+    /// struct Measurements {
+    ///     ts: Vec<NaiveDateTime>,
+    ///     temperature: Vec<f64>,
+    ///     humidity: Vec<Option<f64>>,
+    /// }
+    /// let data: Measurements = resultset.into_columns()?;
+    /// ```
+    fn into_columns<'de, T>(self) -> Result<T, Self::E>
+    where
+        T: serde::de::Deserialize<'de>,
+        <Self::ROW as DeserializableRow>::V: 'de,
+        Self: Sized,
+    {
+        self.into_columns_with(&DeserializationOptions::default())
+    }
+
+    /// A _provided method_ that translates the resultset column-wise into a struct
+    /// or a map of `Vec`s, using the given options.
+    fn into_columns_with<'de, T>(self, options: &DeserializationOptions) -> Result<T, Self::E>
+    where
+        T: serde::de::Deserialize<'de>,
+        <Self::ROW as DeserializableRow>::V: 'de,
+        Self: Sized,
+    {
+        Ok(RsDeserializer::try_new(self, options)?.into_columns()?)
+    }
 }
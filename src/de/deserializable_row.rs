@@ -3,7 +3,7 @@ use std::convert::From;
 use std::marker::Sized;
 
 use crate::de::row_deserializer::RowDeserializer;
-use crate::de::{DbValue, DeserializationError};
+use crate::de::{DbValue, DeserializationError, DeserializationOptions};
 
 /// A minimal interface for the Row type to support the deserialization.
 #[allow(clippy::len_without_is_empty)]
@@ -29,9 +29,20 @@ pub trait DeserializableRow: Sized {
     fn into_typed<'de, T>(self) -> Result<T, Self::E>
     where
         T: serde::de::Deserialize<'de>,
+        Self::V: 'de,
+    {
+        self.into_typed_with(&DeserializationOptions::default())
+    }
+
+    /// Converts the row into a struct, a tuple, or (if applicable) into a plain rust value,
+    /// using the given options.
+    fn into_typed_with<'de, T>(self, options: &DeserializationOptions) -> Result<T, Self::E>
+    where
+        T: serde::de::Deserialize<'de>,
+        Self::V: 'de,
     {
         Ok(serde::de::Deserialize::deserialize(
-            &mut RowDeserializer::new(self),
+            &mut RowDeserializer::new(self, options),
         )?)
     }
 }
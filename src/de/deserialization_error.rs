@@ -22,6 +22,112 @@ pub enum DeserializationError {
     TrailingRows,
     /// The conversion cannot consume all existing columns.
     TrailingCols,
+    /// Deserialization failed at a specific position of the resultset.
+    Located {
+        /// Index of the row within the resultset, if known.
+        row: Option<usize>,
+        /// Index of the column within the row, if known.
+        column: Option<usize>,
+        /// Name of the column, if known.
+        column_name: Option<String>,
+        /// The rust type into which the value should be converted, if known.
+        target_type: Option<&'static str>,
+        /// The error that occured at this position.
+        cause: Box<DeserializationError>,
+    },
+}
+
+impl DeserializationError {
+    // Adds the row index to the error.
+    pub(crate) fn with_row(self, row_idx: usize) -> DeserializationError {
+        let mut e = self.into_located();
+        if let DeserializationError::Located { ref mut row, .. } = e {
+            if row.is_none() {
+                *row = Some(row_idx);
+            }
+        }
+        e
+    }
+
+    // Adds the column index and name to the error.
+    pub(crate) fn with_column(
+        self,
+        col_idx: usize,
+        col_name: Option<&String>,
+    ) -> DeserializationError {
+        let mut e = self.into_located();
+        if let DeserializationError::Located {
+            ref mut column,
+            ref mut column_name,
+            ..
+        } = e
+        {
+            if column.is_none() {
+                *column = Some(col_idx);
+                *column_name = col_name.cloned();
+            }
+        }
+        e
+    }
+
+    // Adds the requested rust type to the error.
+    pub(crate) fn with_target_type(self, t: &'static str) -> DeserializationError {
+        let mut e = self.into_located();
+        if let DeserializationError::Located {
+            ref mut target_type,
+            ..
+        } = e
+        {
+            if target_type.is_none() {
+                *target_type = Some(t);
+            }
+        }
+        e
+    }
+
+    // Only conversion errors are bound to a specific value; all other errors remain unchanged.
+    fn into_located(self) -> DeserializationError {
+        match self {
+            cause @ DeserializationError::ConversionError(_) => DeserializationError::Located {
+                row: None,
+                column: None,
+                column_name: None,
+                target_type: None,
+                cause: Box::new(cause),
+            },
+            e => e,
+        }
+    }
+
+    // Writes e.g. "row 1532, column PRICE (#4): cannot convert into i16".
+    fn fmt_location(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        if let DeserializationError::Located {
+            ref row,
+            ref column,
+            ref column_name,
+            ref target_type,
+            ..
+        } = *self
+        {
+            let mut sep = "";
+            if let Some(row) = row {
+                write!(fmt, "row {}", row)?;
+                sep = ", ";
+            }
+            match (column, column_name) {
+                (Some(idx), Some(name)) => write!(fmt, "{}column {} (#{})", sep, name, idx)?,
+                (Some(idx), None) => write!(fmt, "{}column #{}", sep, idx)?,
+                (None, _) => {}
+            }
+            if let Some(target_type) = target_type {
+                if row.is_some() || column.is_some() {
+                    write!(fmt, ": ")?;
+                }
+                write!(fmt, "cannot convert into {}", target_type)?;
+            }
+        }
+        Ok(())
+    }
 }
 
 impl error::Error for DeserializationError {
@@ -43,12 +149,14 @@ impl error::Error for DeserializationError {
             }
             DeserializationError::TrailingRows => "trailing rows",
             DeserializationError::TrailingCols => "trailing columns",
+            DeserializationError::Located { .. } => "deserialization failed at a specific position",
         }
     }
 
     fn cause(&self) -> Option<&error::Error> {
         match *self {
             DeserializationError::ConversionError(ref e) => Some(e),
+            DeserializationError::Located { ref cause, .. } => Some(&**cause),
             _ => None,
         }
     }
@@ -81,6 +189,10 @@ impl fmt::Debug for DeserializationError {
             DeserializationError::TrailingRows | DeserializationError::TrailingCols => {
                 write!(formatter, "{}", self.description())
             }
+            DeserializationError::Located { ref cause, .. } => {
+                self.fmt_location(formatter)?;
+                write!(formatter, ": {:?}", cause)
+            }
         }
     }
 }
@@ -94,6 +206,10 @@ impl fmt::Display for DeserializationError {
             DeserializationError::NotImplemented(s) => write!(fmt, "{} ", s),
             DeserializationError::TrailingRows => write!(fmt, "TrailingRows"),
             DeserializationError::TrailingCols => write!(fmt, "TrailingCols"),
+            DeserializationError::Located { ref cause, .. } => {
+                self.fmt_location(fmt)?;
+                write!(fmt, ": {}", cause)
+            }
         }
     }
 }
@@ -0,0 +1,272 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::de::ConversionPolicy;
+
+/// Options that control the deserialization of resultsets and rows.
+///
+/// The default options are used by the `into_typed()` methods;
+/// the `into_typed_with()` methods allow specifying other options:
+///
+/// ```ignore
+/// let options = DeserializationOptions::default().nesting_separator(".");
+/// let data: Vec<Customer> = resultset.into_typed_with(&options)?;
+/// ```
+#[derive(Clone)]
+pub struct DeserializationOptions {
+    pub(crate) nesting_separators: Vec<String>,
+    pub(crate) tuple_prefixes: Vec<String>,
+    pub(crate) cardinality: RowCardinality,
+    pub(crate) ignore_trailing_columns: bool,
+    pub(crate) ignore_unknown_columns: bool,
+    pub(crate) ignored_columns_callback: Option<IgnoredColumnsCallback>,
+    pub(crate) column_name_matching: ColumnNameMatching,
+    pub(crate) conversion_policy: ConversionPolicy,
+}
+
+type IgnoredColumnsCallback = Arc<dyn Fn(&[String]) + Send + Sync>;
+
+/// Defines how column names are matched against the field names of the target structs.
+#[derive(Clone)]
+pub enum ColumnNameMatching {
+    /// Column names must be equal to the field names.
+    Exact,
+    /// Column names and field names are compared ignoring ASCII case,
+    /// so that e.g. the column `MY_COL` fills the field `my_col`.
+    CaseInsensitive,
+    /// Column names in UPPER_SNAKE_CASE, CamelCase, or lowerCamelCase are converted
+    /// to snake_case before they are compared with the field names,
+    /// so that e.g. the columns `CUSTOMER_ID`, `CustomerId`, and `customerId`
+    /// fill the field `customer_id`.
+    SnakeCase,
+    /// Column names are converted with the given function before
+    /// they are compared with the field names.
+    Custom(Arc<dyn Fn(&str) -> String + Send + Sync>),
+}
+
+impl ColumnNameMatching {
+    pub(crate) fn normalize<'a>(&self, column_name: &'a str) -> Cow<'a, str> {
+        match *self {
+            ColumnNameMatching::Exact | ColumnNameMatching::CaseInsensitive => {
+                Cow::Borrowed(column_name)
+            }
+            ColumnNameMatching::SnakeCase => Cow::Owned(to_snake_case(column_name)),
+            ColumnNameMatching::Custom(ref f) => Cow::Owned(f(column_name)),
+        }
+    }
+
+    // Compares a field name with a normalized column name.
+    pub(crate) fn eq(&self, field: &str, name: &str) -> bool {
+        match *self {
+            ColumnNameMatching::CaseInsensitive => field.eq_ignore_ascii_case(name),
+            _ => field == name,
+        }
+    }
+
+    // Checks if a normalized column name starts with the given prefix.
+    pub(crate) fn has_prefix(&self, name: &str, prefix: &str) -> bool {
+        match name.get(..prefix.len()) {
+            Some(start) => self.eq(prefix, start),
+            None => false,
+        }
+    }
+}
+
+// Inserts an underscore before each word that starts with an upper-case letter,
+// and converts everything to lower case; e.g. "HTTPServerId" is converted into "http_server_id".
+fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut result = String::with_capacity(name.len() + 4);
+    for (idx, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && idx > 0 {
+            let prev = chars[idx - 1];
+            let next_is_lower = match chars.get(idx + 1) {
+                Some(next) => next.is_lowercase(),
+                None => false,
+            };
+            if prev.is_lowercase() || prev.is_numeric() || (prev.is_uppercase() && next_is_lower) {
+                result.push('_');
+            }
+        }
+        result.extend(c.to_lowercase());
+    }
+    result
+}
+
+impl fmt::Debug for ColumnNameMatching {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ColumnNameMatching::Exact => write!(fmt, "Exact"),
+            ColumnNameMatching::CaseInsensitive => write!(fmt, "CaseInsensitive"),
+            ColumnNameMatching::SnakeCase => write!(fmt, "SnakeCase"),
+            ColumnNameMatching::Custom(_) => write!(fmt, "Custom"),
+        }
+    }
+}
+
+/// Defines which number of rows is accepted if a resultset is converted into
+/// a single-row target, like a struct, a tuple, a plain value, or an `Option` of these.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RowCardinality {
+    /// The resultset must contain exactly one row, also for `Option` targets.
+    ExactlyOne,
+    /// The resultset must not contain more than one row;
+    /// an empty resultset is converted into `None` for `Option` targets.
+    AtMostOne,
+    /// The first row is converted, further rows are discarded;
+    /// an empty resultset is converted into `None` for `Option` targets.
+    FirstRow,
+}
+
+impl Default for DeserializationOptions {
+    fn default() -> DeserializationOptions {
+        DeserializationOptions {
+            nesting_separators: vec!["_".to_string(), ".".to_string()],
+            tuple_prefixes: Vec::new(),
+            cardinality: RowCardinality::AtMostOne,
+            ignore_trailing_columns: false,
+            ignore_unknown_columns: false,
+            ignored_columns_callback: None,
+            column_name_matching: ColumnNameMatching::Exact,
+            conversion_policy: ConversionPolicy::default(),
+        }
+    }
+}
+
+impl fmt::Debug for DeserializationOptions {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("DeserializationOptions")
+            .field("nesting_separators", &self.nesting_separators)
+            .field("tuple_prefixes", &self.tuple_prefixes)
+            .field("cardinality", &self.cardinality)
+            .field("ignore_trailing_columns", &self.ignore_trailing_columns)
+            .field("ignore_unknown_columns", &self.ignore_unknown_columns)
+            .field(
+                "ignored_columns_callback",
+                &self.ignored_columns_callback.as_ref().map(|_| "Fn(&[String])"),
+            )
+            .field("column_name_matching", &self.column_name_matching)
+            .field("conversion_policy", &self.conversion_policy)
+            .finish()
+    }
+}
+
+impl DeserializationOptions {
+    /// Sets the separator between the name of a struct field and the names
+    /// of the fields of the nested struct, with which the column names are built.
+    ///
+    /// By default, both `_` and `.` are accepted, so that e.g. the columns
+    /// `address_street` or `address.street` fill the field `street` of the field `address`.
+    /// Columns like `id_ext` are not assigned to a field `id` if the row also has a column `id`,
+    /// or if `id` is no struct, map, or `Vec`; they are then unknown columns,
+    /// but since the field `id` has no column then, the deserialization fails.
+    ///
+    /// A field `address: Option<Address>` is `None` if all columns with the prefix `address_`
+    /// are NULL, as it is the case with a LEFT JOIN that finds no address.
+    pub fn nesting_separator<S: Into<String>>(mut self, separator: S) -> DeserializationOptions {
+        self.nesting_separators = vec![separator.into()];
+        self
+    }
+
+    /// Sets the column name prefixes of the structs in a tuple, like `(User, Order)`.
+    ///
+    /// The i-th struct of the tuple is filled from the consecutive columns that start
+    /// with the i-th prefix; the prefix is removed before the column names are matched
+    /// against the field names.
+    ///
+    /// Without a prefix, a struct in a tuple takes the consecutive columns whose names are
+    /// field names of the struct, or, if the first column is no field name,
+    /// the consecutive columns that share the part of the first column name up to the
+    /// first nesting separator.
+    pub fn tuple_prefixes<I, S>(mut self, prefixes: I) -> DeserializationOptions
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.tuple_prefixes = prefixes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the number of rows that is accepted for single-row targets.
+    ///
+    /// The default is `RowCardinality::AtMostOne`.
+    pub fn cardinality(mut self, cardinality: RowCardinality) -> DeserializationOptions {
+        self.cardinality = cardinality;
+        self
+    }
+
+    /// Defines if a row with multiple columns can be converted into a plain value,
+    /// by converting the first column and ignoring the others.
+    ///
+    /// By default, this fails with `DeserializationError::TrailingCols`.
+    pub fn ignore_trailing_columns(mut self, ignore: bool) -> DeserializationOptions {
+        self.ignore_trailing_columns = ignore;
+        self
+    }
+
+    /// Defines if columns for which the target struct has no field are skipped.
+    ///
+    /// By default, such columns let the deserialization fail with
+    /// `DeserializationError::UnknownField`, so that e.g. a `select *` breaks if
+    /// a column is added to the table.
+    /// The names of the skipped columns are logged with level debug, and are handed over
+    /// to the callback that can be registered with `on_ignored_columns()`.
+    pub fn ignore_unknown_columns(mut self, ignore: bool) -> DeserializationOptions {
+        self.ignore_unknown_columns = ignore;
+        self
+    }
+
+    /// Registers a callback that is called with the names of the columns that were skipped
+    /// because of `ignore_unknown_columns(true)`.
+    ///
+    /// The callback is called once per resultset (or row, if single rows are converted),
+    /// and only if columns were skipped.
+    pub fn on_ignored_columns<F>(mut self, callback: F) -> DeserializationOptions
+    where
+        F: Fn(&[String]) + Send + Sync + 'static,
+    {
+        self.ignored_columns_callback = Some(Arc::new(callback));
+        self
+    }
+
+    /// Defines how column names are matched against field names.
+    ///
+    /// The default is `ColumnNameMatching::Exact`.
+    ///
+    /// ```ignore
+    /// let options = DeserializationOptions::default()
+    ///     .column_name_matching(ColumnNameMatching::SnakeCase);
+    /// ```
+    pub fn column_name_matching(mut self, matching: ColumnNameMatching) -> DeserializationOptions {
+        self.column_name_matching = matching;
+        self
+    }
+
+    /// Sets a function that converts the column names before they are
+    /// compared with the field names.
+    ///
+    /// This is a shortcut for `column_name_matching(ColumnNameMatching::Custom(...))`.
+    pub fn column_name_normalizer<F>(self, normalizer: F) -> DeserializationOptions
+    where
+        F: Fn(&str) -> String + Send + Sync + 'static,
+    {
+        self.column_name_matching(ColumnNameMatching::Custom(Arc::new(normalizer)))
+    }
+
+    /// Defines which numeric conversions are applied to the field values.
+    ///
+    /// The default is `ConversionPolicy::RangeChecked`. The policy can only be applied to values
+    /// for which the driver provides the canonical representation, see `DbValue::canonical()`;
+    /// with another than the default policy, the conversion of other values into numbers fails
+    /// with `DeserializationError::Usage`.
+    ///
+    /// ```ignore
+    /// let options = DeserializationOptions::default()
+    ///     .conversion_policy(ConversionPolicy::ParseFromString);
+    /// ```
+    pub fn conversion_policy(mut self, policy: ConversionPolicy) -> DeserializationOptions {
+        self.conversion_policy = policy;
+        self
+    }
+}
@@ -1,10 +1,18 @@
 use log::trace;
 use serde;
+use serde::de::IntoDeserializer;
 
-use crate::de::{DbValue, DbValueInto, DeserializationError, DeserializationResult};
+use crate::de::canonical_db_value::FromCanonical;
+use crate::de::{
+    ConversionError, ConversionPolicy, DbValue, DbValueInto, DeserializationError,
+    DeserializationResult,
+};
 
 /// Deserialize a single `DbValue` into a normal rust type.
-pub struct FieldDeserializer<DBV>(DBV);
+pub struct FieldDeserializer<DBV> {
+    value: DBV,
+    policy: ConversionPolicy,
+}
 
 impl<DBV> FieldDeserializer<DBV>
 where
@@ -12,11 +20,60 @@ where
 {
     pub fn new(value: DBV) -> FieldDeserializer<DBV> {
         trace!("FieldDeserializer::new()");
-        FieldDeserializer(value)
+        FieldDeserializer {
+            value,
+            policy: ConversionPolicy::default(),
+        }
+    }
+
+    pub fn with_policy(mut self, policy: ConversionPolicy) -> FieldDeserializer<DBV> {
+        self.policy = policy;
+        self
+    }
+
+    fn convert<T>(self, target_type: &'static str) -> DeserializationResult<T>
+    where
+        DBV: DbValueInto<T>,
+    {
+        DbValueInto::<T>::try_into(self.value)
+            .map_err(|e| DeserializationError::from(e).with_target_type(target_type))
+    }
+
+    fn convert_number<T>(self, target_type: &'static str) -> DeserializationResult<T>
+    where
+        DBV: DbValueInto<T>,
+        T: FromCanonical,
+    {
+        self.convert_canonical(target_type, DbValueInto::<T>::try_into)
+    }
+
+    // Applies the conversion policy if the value provides its canonical representation;
+    // other values are converted by the driver, which is only possible with the default policy.
+    fn convert_canonical<T, F>(
+        self,
+        target_type: &'static str,
+        convert: F,
+    ) -> DeserializationResult<T>
+    where
+        T: FromCanonical,
+        F: FnOnce(DBV) -> Result<T, ConversionError>,
+    {
+        let result = match self.value.canonical() {
+            Some(value) => T::from_canonical(value, self.policy),
+            None if self.policy == ConversionPolicy::default() => convert(self.value),
+            None => {
+                return Err(DeserializationError::Usage(format!(
+                    "cannot convert into {} with conversion policy {:?}, because the value \
+                     does not provide its canonical representation",
+                    target_type, self.policy
+                )));
+            }
+        };
+        result.map_err(|e| DeserializationError::from(e).with_target_type(target_type))
     }
 }
 
-impl<'x, 'a, DBV: DbValue> serde::Deserializer<'x> for FieldDeserializer<DBV> {
+impl<'x, 'a, DBV: DbValue + 'x> serde::Deserializer<'x> for FieldDeserializer<DBV> {
     type Error = DeserializationError;
 
     fn deserialize_any<V>(self, visitor: V) -> DeserializationResult<V::Value>
@@ -24,7 +81,7 @@ impl<'x, 'a, DBV: DbValue> serde::Deserializer<'x> for FieldDeserializer<DBV> {
         V: serde::de::Visitor<'x>,
     {
         trace!("FieldDeserializer::deserialize_any()");
-        visitor.visit_string(self.0.try_into()?)
+        visitor.visit_string(self.convert("String")?)
     }
 
     fn deserialize_bool<V>(self, visitor: V) -> DeserializationResult<V::Value>
@@ -32,7 +89,7 @@ impl<'x, 'a, DBV: DbValue> serde::Deserializer<'x> for FieldDeserializer<DBV> {
         V: serde::de::Visitor<'x>,
     {
         trace!("FieldDeserializer::deserialize_bool()");
-        visitor.visit_bool(self.0.try_into()?)
+        visitor.visit_bool(self.convert_number("bool")?)
     }
 
     fn deserialize_u8<V>(self, visitor: V) -> DeserializationResult<V::Value>
@@ -40,7 +97,7 @@ impl<'x, 'a, DBV: DbValue> serde::Deserializer<'x> for FieldDeserializer<DBV> {
         V: serde::de::Visitor<'x>,
     {
         trace!("FieldDeserializer::deserialize_u8()");
-        visitor.visit_u8(self.0.try_into()?)
+        visitor.visit_u8(self.convert_number("u8")?)
     }
 
     fn deserialize_u16<V>(self, visitor: V) -> DeserializationResult<V::Value>
@@ -48,7 +105,7 @@ impl<'x, 'a, DBV: DbValue> serde::Deserializer<'x> for FieldDeserializer<DBV> {
         V: serde::de::Visitor<'x>,
     {
         trace!("FieldDeserializer::deserialize_u16()");
-        visitor.visit_u16(self.0.try_into()?)
+        visitor.visit_u16(self.convert_number("u16")?)
     }
 
     fn deserialize_u32<V>(self, visitor: V) -> DeserializationResult<V::Value>
@@ -56,7 +113,7 @@ impl<'x, 'a, DBV: DbValue> serde::Deserializer<'x> for FieldDeserializer<DBV> {
         V: serde::de::Visitor<'x>,
     {
         trace!("FieldDeserializer::deserialize_u32()");
-        visitor.visit_u32(self.0.try_into()?)
+        visitor.visit_u32(self.convert_number("u32")?)
     }
 
     fn deserialize_u64<V>(self, visitor: V) -> DeserializationResult<V::Value>
@@ -64,7 +121,15 @@ impl<'x, 'a, DBV: DbValue> serde::Deserializer<'x> for FieldDeserializer<DBV> {
         V: serde::de::Visitor<'x>,
     {
         trace!("FieldDeserializer::deserialize_u64()");
-        visitor.visit_u64(self.0.try_into()?)
+        visitor.visit_u64(self.convert_number("u64")?)
+    }
+
+    fn deserialize_u128<V>(self, visitor: V) -> DeserializationResult<V::Value>
+    where
+        V: serde::de::Visitor<'x>,
+    {
+        trace!("FieldDeserializer::deserialize_u128()");
+        visitor.visit_u128(self.convert_canonical("u128", DbValue::try_into_u128)?)
     }
 
     fn deserialize_i8<V>(self, visitor: V) -> DeserializationResult<V::Value>
@@ -72,7 +137,7 @@ impl<'x, 'a, DBV: DbValue> serde::Deserializer<'x> for FieldDeserializer<DBV> {
         V: serde::de::Visitor<'x>,
     {
         trace!("FieldDeserializer::deserialize_i8()");
-        visitor.visit_i8(self.0.try_into()?)
+        visitor.visit_i8(self.convert_number("i8")?)
     }
 
     fn deserialize_i16<V>(self, visitor: V) -> DeserializationResult<V::Value>
@@ -80,7 +145,7 @@ impl<'x, 'a, DBV: DbValue> serde::Deserializer<'x> for FieldDeserializer<DBV> {
         V: serde::de::Visitor<'x>,
     {
         trace!("FieldDeserializer::deserialize_i16()");
-        visitor.visit_i16(self.0.try_into()?)
+        visitor.visit_i16(self.convert_number("i16")?)
     }
 
     fn deserialize_i32<V>(self, visitor: V) -> DeserializationResult<V::Value>
@@ -88,7 +153,7 @@ impl<'x, 'a, DBV: DbValue> serde::Deserializer<'x> for FieldDeserializer<DBV> {
         V: serde::de::Visitor<'x>,
     {
         trace!("FieldDeserializer::deserialize_i32()");
-        visitor.visit_i32(self.0.try_into()?)
+        visitor.visit_i32(self.convert_number("i32")?)
     }
 
     fn deserialize_i64<V>(self, visitor: V) -> DeserializationResult<V::Value>
@@ -96,7 +161,15 @@ impl<'x, 'a, DBV: DbValue> serde::Deserializer<'x> for FieldDeserializer<DBV> {
         V: serde::de::Visitor<'x>,
     {
         trace!("FieldDeserializer::deserialize_i64()");
-        visitor.visit_i64(self.0.try_into()?)
+        visitor.visit_i64(self.convert_number("i64")?)
+    }
+
+    fn deserialize_i128<V>(self, visitor: V) -> DeserializationResult<V::Value>
+    where
+        V: serde::de::Visitor<'x>,
+    {
+        trace!("FieldDeserializer::deserialize_i128()");
+        visitor.visit_i128(self.convert_canonical("i128", DbValue::try_into_i128)?)
     }
 
     fn deserialize_f32<V>(self, visitor: V) -> DeserializationResult<V::Value>
@@ -104,7 +177,7 @@ impl<'x, 'a, DBV: DbValue> serde::Deserializer<'x> for FieldDeserializer<DBV> {
         V: serde::de::Visitor<'x>,
     {
         trace!("FieldDeserializer::deserialize_f32()");
-        visitor.visit_f32(self.0.try_into()?)
+        visitor.visit_f32(self.convert_number("f32")?)
     }
 
     fn deserialize_f64<V>(self, visitor: V) -> DeserializationResult<V::Value>
@@ -112,7 +185,7 @@ impl<'x, 'a, DBV: DbValue> serde::Deserializer<'x> for FieldDeserializer<DBV> {
         V: serde::de::Visitor<'x>,
     {
         trace!("FieldDeserializer::deserialize_f64()");
-        visitor.visit_f64(self.0.try_into()?)
+        visitor.visit_f64(self.convert_number("f64")?)
     }
 
     fn deserialize_char<V>(self, _visitor: V) -> DeserializationResult<V::Value>
@@ -128,8 +201,11 @@ impl<'x, 'a, DBV: DbValue> serde::Deserializer<'x> for FieldDeserializer<DBV> {
     where
         V: serde::de::Visitor<'x>,
     {
-        trace!("FieldDeserializer::deserialize_str(), delegates to deserialize_string()");
-        self.deserialize_string(visitor)
+        trace!("FieldDeserializer::deserialize_str()");
+        match self.value.as_str() {
+            Some(s) => visitor.visit_borrowed_str(s),
+            None => self.deserialize_string(visitor),
+        }
     }
 
     fn deserialize_string<V>(self, visitor: V) -> DeserializationResult<V::Value>
@@ -137,7 +213,7 @@ impl<'x, 'a, DBV: DbValue> serde::Deserializer<'x> for FieldDeserializer<DBV> {
         V: serde::de::Visitor<'x>,
     {
         trace!("FieldDeserializer::deserialize_string()");
-        visitor.visit_string(self.0.try_into()?)
+        visitor.visit_string(self.convert("String")?)
     }
 
     fn deserialize_unit<V>(self, _visitor: V) -> DeserializationResult<V::Value>
@@ -154,7 +230,7 @@ impl<'x, 'a, DBV: DbValue> serde::Deserializer<'x> for FieldDeserializer<DBV> {
         V: serde::de::Visitor<'x>,
     {
         trace!("FieldDeserializer::deserialize_option()");
-        if self.0.is_null() {
+        if self.value.is_null() {
             visitor.visit_none()
         } else {
             visitor.visit_some(self)
@@ -237,15 +313,18 @@ impl<'x, 'a, DBV: DbValue> serde::Deserializer<'x> for FieldDeserializer<DBV> {
         V: serde::de::Visitor<'x>,
     {
         trace!("FieldDeserializer::deserialize_bytes()");
-        visitor.visit_bytes(&DbValueInto::<Vec<u8>>::try_into(self.0)?)
+        match self.value.as_bytes() {
+            Some(b) => visitor.visit_borrowed_bytes(b),
+            None => visitor.visit_byte_buf(self.convert("Vec<u8>")?),
+        }
     }
 
     fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: serde::de::Visitor<'x>,
     {
-        trace!("FieldDeserializer::deserialize_bytes()");
-        visitor.visit_bytes(&DbValueInto::<Vec<u8>>::try_into(self.0)?)
+        trace!("FieldDeserializer::deserialize_byte_buf()");
+        visitor.visit_byte_buf(self.convert("Vec<u8>")?)
     }
 
     fn deserialize_tuple<V>(self, _len: usize, _visitor: V) -> DeserializationResult<V::Value>
@@ -257,18 +336,40 @@ impl<'x, 'a, DBV: DbValue> serde::Deserializer<'x> for FieldDeserializer<DBV> {
         ))
     }
 
+    // Only unit variants can be represented by a single database value.
+    // Integer values (see DbValue::is_integer()) are read as u32 and taken as the variant index,
+    // all other values are read as String and matched against the variant names.
     fn deserialize_enum<V>(
         self,
         _name: &'static str,
-        _variants: &'static [&'static str],
-        _visitor: V,
+        variants: &'static [&'static str],
+        visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: serde::de::Visitor<'x>,
     {
-        Err(DeserializationError::NotImplemented(
-            "FieldDeserializer::deserialize_enum()",
-        ))
+        trace!("FieldDeserializer::deserialize_enum()");
+        if self.value.is_integer() {
+            let idx: u32 = self.convert_number("u32")?;
+            if (idx as usize) < variants.len() {
+                visitor.visit_enum(IntoDeserializer::<DeserializationError>::into_deserializer(idx))
+            } else {
+                Err(DeserializationError::Usage(format!(
+                    "value {} is no index of the variants {:?}",
+                    idx, variants
+                )))
+            }
+        } else {
+            let s: String = self.convert("String")?;
+            if variants.contains(&s.as_str()) {
+                visitor.visit_enum(IntoDeserializer::<DeserializationError>::into_deserializer(s))
+            } else {
+                Err(DeserializationError::Usage(format!(
+                    "value \"{}\" matches none of the variants {:?}",
+                    s, variants
+                )))
+            }
+        }
     }
 
     fn deserialize_identifier<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
@@ -1,10 +1,16 @@
-use log::trace;
+use log::{debug, trace};
 use serde;
 use serde::de::Deserialize as SD;
+use serde::de::IntoDeserializer;
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::rc::Rc;
 
+use crate::de::buffered_row::BufferedRow;
 use crate::de::field_deserializer::FieldDeserializer;
 use crate::de::{
-    DbValue, DbValueInto, DeserializableRow, DeserializationError, DeserializationResult,
+    DbValue, DeserializableRow, DeserializationError, DeserializationOptions,
+    DeserializationResult,
 };
 
 enum MCD {
@@ -13,29 +19,140 @@ enum MCD {
     Done,
 }
 
+// The columns from which a struct is filled.
+enum Columns {
+    // all remaining columns
+    All,
+    // the consecutive columns that start with the prefix
+    Prefixed(String),
+    // the consecutive columns whose names are field names, each at most once
+    Fields,
+}
+
 // Deserialize a single Row into a normal rust type.
-pub struct RowDeserializer<ROW> {
+pub struct RowDeserializer<'o, ROW> {
     row: ROW,
     cols_treat: MCD,
+    options: &'o DeserializationOptions,
+    // column name prefix of the nested struct that is deserialized next
+    nested_prefix: Option<String>,
+    // index of the tuple element that is deserialized next
+    tuple_element: Option<usize>,
+    // further rows of a group, with their row index, which contribute to Vec fields
+    following_rows: VecDeque<(usize, ROW)>,
+    // names of the columns that were skipped because the target has no field for them
+    ignored_columns: Vec<String>,
+    report_ignored_columns: bool,
 }
 
-impl<ROW> RowDeserializer<ROW>
+impl<'o, ROW> RowDeserializer<'o, ROW>
 where
     ROW: DeserializableRow,
     <ROW as DeserializableRow>::V: DbValue,
 {
-    pub fn new(row: ROW) -> RowDeserializer<ROW> {
+    pub fn new(row: ROW, options: &'o DeserializationOptions) -> RowDeserializer<'o, ROW> {
         trace!("RowDeserializer::new()");
         let cols_treat = match row.len() {
             1 => MCD::Can,
             _ => MCD::Must,
         };
-        RowDeserializer { cols_treat, row }
+        RowDeserializer {
+            cols_treat,
+            row,
+            options,
+            nested_prefix: None,
+            tuple_element: None,
+            following_rows: VecDeque::new(),
+            ignored_columns: Vec::new(),
+            report_ignored_columns: true,
+        }
+    }
+
+    // Suppresses the report of ignored columns, which is only needed for the first row
+    // of a resultset.
+    pub fn without_report(mut self) -> RowDeserializer<'o, ROW> {
+        self.report_ignored_columns = false;
+        self
+    }
+
+    fn report_ignored_columns(&mut self) {
+        if self.report_ignored_columns && !self.ignored_columns.is_empty() {
+            debug!("Ignored columns without target field: {:?}", self.ignored_columns);
+            if let Some(ref callback) = self.options.ignored_columns_callback {
+                callback(&self.ignored_columns);
+            }
+        }
+        self.ignored_columns.clear();
+    }
+
+    // A deserializer for a group of rows: the first row is deserialized as usual,
+    // the following rows contribute only the elements of Vec fields.
+    pub fn new_group(
+        row: ROW,
+        following_rows: VecDeque<(usize, ROW)>,
+        options: &'o DeserializationOptions,
+    ) -> RowDeserializer<'o, ROW> {
+        let mut rd = RowDeserializer::new(row, options);
+        rd.following_rows = following_rows;
+        rd
+    }
+
+    // A deserializer for a row that is converted into a map entry:
+    // the first column is the key, the remaining columns form the value.
+    pub fn new_map_entry(
+        row: ROW,
+        options: &'o DeserializationOptions,
+    ) -> RowDeserializer<'o, ROW> {
+        let mut rd = RowDeserializer::new(row, options);
+        rd.cols_treat = MCD::Done;
+        rd
+    }
+
+    // Lets the remaining columns be deserialized like a complete row,
+    // after the key of a map entry was taken from the first column.
+    pub fn treat_remaining_columns_as_row(&mut self) {
+        self.cols_treat = match self.row.len() {
+            1 => MCD::Can,
+            _ => MCD::Must,
+        };
+    }
+
+    // Returns the name of the column, normalized for matching it with the field names.
+    fn matching_name(&self, idx: usize) -> Option<Cow<'_, str>> {
+        self.get_fieldname(idx)
+            .map(|name| self.options.column_name_matching.normalize(name))
+    }
+
+    fn current_matching_name(&self) -> Option<Cow<'_, str>> {
+        match self.row.len() {
+            0 => None,
+            len => self.matching_name(self.row.number_of_fields() - len),
+        }
+    }
+
+    // Determines the columns of a struct that is a tuple element.
+    fn element_columns(&self, element: usize, fields: &[&str]) -> Columns {
+        if let Some(prefix) = self.options.tuple_prefixes.get(element) {
+            return Columns::Prefixed(prefix.clone());
+        }
+        let matching = &self.options.column_name_matching;
+        match self.current_matching_name() {
+            Some(ref name) if !fields.iter().any(|field| matching.eq(field, name)) => self
+                .options
+                .nesting_separators
+                .iter()
+                .filter_map(|sep| name.find(sep.as_str()).map(|pos| pos + sep.len()))
+                .min()
+                .map_or(Columns::Fields, |end| Columns::Prefixed(name[..end].to_string())),
+            _ => Columns::Fields,
+        }
     }
 
     fn value_deserialization_allowed(&self) -> DeserializationResult<()> {
         match self.cols_treat {
-            MCD::Must => Err(DeserializationError::TrailingCols),
+            MCD::Must if !self.options.ignore_trailing_columns => {
+                Err(DeserializationError::TrailingCols)
+            }
             _ => Ok(()),
         }
     }
@@ -44,19 +161,69 @@ where
         self.row.fieldname(idx)
     }
 
+    // Checks if the current column belongs to the nested struct with the given prefix.
+    fn current_column_has_prefix(&self, prefix: &str) -> bool {
+        match self.current_matching_name() {
+            Some(name) => self.options.column_name_matching.has_prefix(&name, prefix),
+            None => false,
+        }
+    }
+
+    // Removes the consecutive columns that belong to the nested struct with the given prefix,
+    // and returns them as a row of their own.
+    fn take_prefixed_columns(&mut self, prefix: &str) -> BufferedRow<ROW::V> {
+        let mut values = VecDeque::new();
+        while self.current_column_has_prefix(prefix) {
+            match self.row.next() {
+                Some(value) => values.push_back(value),
+                None => break,
+            }
+        }
+        let end = self.row.number_of_fields() - self.row.len();
+        let fieldnames = (0..end)
+            .map(|idx| self.get_fieldname(idx).cloned().unwrap_or_default())
+            .collect();
+        BufferedRow::from_values(values, Rc::new(fieldnames))
+    }
+
     fn next_value(&mut self) -> DeserializationResult<ROW::V> {
         trace!("RowDeserializer::next_value()");
         self.value_deserialization_allowed()?;
+        if let Some(prefix) = self.nested_prefix.take() {
+            // the column names only look like those of a nested struct, but the field is a value
+            let idx = self.row.number_of_fields() - self.row.len();
+            let fieldname = self.get_fieldname(idx).cloned().unwrap_or_default();
+            if self.options.ignore_unknown_columns {
+                while self.current_column_has_prefix(&prefix) {
+                    let idx = self.row.number_of_fields() - self.row.len();
+                    let name = self.get_fieldname(idx).cloned().unwrap_or_default();
+                    self.row.next();
+                    self.ignored_columns.push(name);
+                }
+                return Err(DeserializationError::Usage(format!(
+                    "no column for the field, column {} and the following columns with \
+                     prefix {} are ignored",
+                    fieldname, prefix
+                )));
+            }
+            return Err(DeserializationError::UnknownField(fieldname));
+        }
         match self.row.next() {
             Some(tv) => Ok(tv),
             None => Err(impl_err("next_value(): no more value found in row")),
         }
     }
+
+    fn next_field(&mut self) -> DeserializationResult<FieldDeserializer<ROW::V>> {
+        Ok(FieldDeserializer::new(self.next_value()?)
+            .with_policy(self.options.conversion_policy))
+    }
 }
 
-impl<'x, 'a, ROW: DeserializableRow> serde::Deserializer<'x> for &'a mut RowDeserializer<ROW>
+impl<'x, 'a, 'o, ROW: DeserializableRow> serde::Deserializer<'x>
+    for &'a mut RowDeserializer<'o, ROW>
 where
-    <ROW as DeserializableRow>::V: DbValue,
+    <ROW as DeserializableRow>::V: DbValue + 'x,
 {
     type Error = DeserializationError;
 
@@ -65,7 +232,7 @@ where
         V: serde::de::Visitor<'x>,
     {
         trace!("RowDeserializer::deserialize_any()");
-        visitor.visit_string(SD::deserialize(FieldDeserializer::new(self.next_value()?))?)
+        visitor.visit_string(SD::deserialize(self.next_field()?)?)
     }
 
     fn deserialize_bool<V>(self, visitor: V) -> DeserializationResult<V::Value>
@@ -73,7 +240,7 @@ where
         V: serde::de::Visitor<'x>,
     {
         trace!("RowDeserializer::deserialize_bool()");
-        visitor.visit_bool(SD::deserialize(FieldDeserializer::new(self.next_value()?))?)
+        visitor.visit_bool(SD::deserialize(self.next_field()?)?)
     }
 
     fn deserialize_u8<V>(self, visitor: V) -> DeserializationResult<V::Value>
@@ -81,7 +248,7 @@ where
         V: serde::de::Visitor<'x>,
     {
         trace!("RowDeserializer::deserialize_u8()");
-        visitor.visit_u8(SD::deserialize(FieldDeserializer::new(self.next_value()?))?)
+        visitor.visit_u8(SD::deserialize(self.next_field()?)?)
     }
 
     fn deserialize_u16<V>(self, visitor: V) -> DeserializationResult<V::Value>
@@ -89,7 +256,7 @@ where
         V: serde::de::Visitor<'x>,
     {
         trace!("RowDeserializer::deserialize_u16()");
-        visitor.visit_u16(SD::deserialize(FieldDeserializer::new(self.next_value()?))?)
+        visitor.visit_u16(SD::deserialize(self.next_field()?)?)
     }
 
     fn deserialize_u32<V>(self, visitor: V) -> DeserializationResult<V::Value>
@@ -97,7 +264,7 @@ where
         V: serde::de::Visitor<'x>,
     {
         trace!("RowDeserializer::deserialize_u32()");
-        visitor.visit_u32(SD::deserialize(FieldDeserializer::new(self.next_value()?))?)
+        visitor.visit_u32(SD::deserialize(self.next_field()?)?)
     }
 
     fn deserialize_u64<V>(self, visitor: V) -> DeserializationResult<V::Value>
@@ -105,7 +272,15 @@ where
         V: serde::de::Visitor<'x>,
     {
         trace!("RowDeserializer::deserialize_u64()");
-        visitor.visit_u64(SD::deserialize(FieldDeserializer::new(self.next_value()?))?)
+        visitor.visit_u64(SD::deserialize(self.next_field()?)?)
+    }
+
+    fn deserialize_u128<V>(self, visitor: V) -> DeserializationResult<V::Value>
+    where
+        V: serde::de::Visitor<'x>,
+    {
+        trace!("RowDeserializer::deserialize_u128()");
+        visitor.visit_u128(SD::deserialize(self.next_field()?)?)
     }
 
     fn deserialize_i8<V>(self, visitor: V) -> DeserializationResult<V::Value>
@@ -113,7 +288,7 @@ where
         V: serde::de::Visitor<'x>,
     {
         trace!("RowDeserializer::deserialize_i8()");
-        visitor.visit_i8(SD::deserialize(FieldDeserializer::new(self.next_value()?))?)
+        visitor.visit_i8(SD::deserialize(self.next_field()?)?)
     }
 
     fn deserialize_i16<V>(self, visitor: V) -> DeserializationResult<V::Value>
@@ -121,7 +296,7 @@ where
         V: serde::de::Visitor<'x>,
     {
         trace!("RowDeserializer::deserialize_i16()");
-        visitor.visit_i16(SD::deserialize(FieldDeserializer::new(self.next_value()?))?)
+        visitor.visit_i16(SD::deserialize(self.next_field()?)?)
     }
 
     fn deserialize_i32<V>(self, visitor: V) -> DeserializationResult<V::Value>
@@ -129,7 +304,7 @@ where
         V: serde::de::Visitor<'x>,
     {
         trace!("RowDeserializer::deserialize_i32()");
-        visitor.visit_i32(SD::deserialize(FieldDeserializer::new(self.next_value()?))?)
+        visitor.visit_i32(SD::deserialize(self.next_field()?)?)
     }
 
     fn deserialize_i64<V>(self, visitor: V) -> DeserializationResult<V::Value>
@@ -137,7 +312,15 @@ where
         V: serde::de::Visitor<'x>,
     {
         trace!("RowDeserializer::deserialize_i64()");
-        visitor.visit_i64(SD::deserialize(FieldDeserializer::new(self.next_value()?))?)
+        visitor.visit_i64(SD::deserialize(self.next_field()?)?)
+    }
+
+    fn deserialize_i128<V>(self, visitor: V) -> DeserializationResult<V::Value>
+    where
+        V: serde::de::Visitor<'x>,
+    {
+        trace!("RowDeserializer::deserialize_i128()");
+        visitor.visit_i128(SD::deserialize(self.next_field()?)?)
     }
 
     fn deserialize_f32<V>(self, visitor: V) -> DeserializationResult<V::Value>
@@ -145,7 +328,7 @@ where
         V: serde::de::Visitor<'x>,
     {
         trace!("RowDeserializer::deserialize_f32()");
-        visitor.visit_f32(SD::deserialize(FieldDeserializer::new(self.next_value()?))?)
+        visitor.visit_f32(SD::deserialize(self.next_field()?)?)
     }
 
     fn deserialize_f64<V>(self, visitor: V) -> DeserializationResult<V::Value>
@@ -153,7 +336,7 @@ where
         V: serde::de::Visitor<'x>,
     {
         trace!("RowDeserializer::deserialize_f64()");
-        visitor.visit_f64(SD::deserialize(FieldDeserializer::new(self.next_value()?))?)
+        visitor.visit_f64(SD::deserialize(self.next_field()?)?)
     }
 
     fn deserialize_char<V>(self, _visitor: V) -> DeserializationResult<V::Value>
@@ -169,8 +352,8 @@ where
     where
         V: serde::de::Visitor<'x>,
     {
-        trace!("RowDeserializer::deserialize_str(), delegates to deserialize_string()");
-        self.deserialize_string(visitor)
+        trace!("RowDeserializer::deserialize_str()");
+        self.next_field()?.deserialize_str(visitor)
     }
 
     fn deserialize_string<V>(self, visitor: V) -> DeserializationResult<V::Value>
@@ -178,26 +361,49 @@ where
         V: serde::de::Visitor<'x>,
     {
         trace!("RowDeserializer::deserialize_string()");
-        visitor.visit_string(SD::deserialize(FieldDeserializer::new(self.next_value()?))?)
+        visitor.visit_string(SD::deserialize(self.next_field()?)?)
     }
 
-    fn deserialize_unit<V>(self, _visitor: V) -> DeserializationResult<V::Value>
+    // Only an empty row can be converted into ().
+    fn deserialize_unit<V>(self, visitor: V) -> DeserializationResult<V::Value>
     where
         V: serde::de::Visitor<'x>,
     {
-        Err(DeserializationError::NotImplemented(
-            "RowDeserializer::deserialize_unit()",
-        ))
+        trace!("RowDeserializer::deserialize_unit()");
+        match self.row.len() {
+            0 => visitor.visit_unit(),
+            _ => Err(DeserializationError::TrailingCols),
+        }
     }
 
+    // An optional nested struct, e.g. from a LEFT JOIN, is None if all its columns are NULL.
     fn deserialize_option<V>(self, visitor: V) -> DeserializationResult<V::Value>
     where
         V: serde::de::Visitor<'x>,
     {
         trace!("RowDeserializer::deserialize_option()");
-        FieldDeserializer::new(self.next_value()?).deserialize_option(visitor)
+        match self.nested_prefix.take() {
+            Some(prefix) => {
+                let row = self.take_prefixed_columns(&prefix);
+                if (0..row.number_of_fields())
+                    .filter_map(|idx| row.value(idx))
+                    .all(DbValue::is_null)
+                {
+                    return visitor.visit_none();
+                }
+                let mut nested = RowDeserializer::new(row, self.options);
+                nested.cols_treat = MCD::Done;
+                nested.nested_prefix = Some(prefix);
+                let result = visitor.visit_some(&mut nested);
+                self.ignored_columns.append(&mut nested.ignored_columns);
+                result
+            }
+            None => self.next_field()?.deserialize_option(visitor),
+        }
     }
 
+    // A nested Vec is filled from the columns with the field name as prefix,
+    // in the current row and in the following rows of the group.
     #[inline]
     fn deserialize_seq<V>(mut self, visitor: V) -> DeserializationResult<V::Value>
     where
@@ -205,9 +411,12 @@ where
     {
         trace!("RowDeserializer::deserialize_seq()");
         match self.cols_treat {
-            MCD::Done => Err(impl_err(
-                "double-nesting (struct/tuple in struct/tuple) not possible",
-            )),
+            MCD::Done => match self.nested_prefix.take() {
+                Some(prefix) => visitor.visit_seq(GroupSeqVisitor::new(self, prefix)),
+                None => Err(impl_err(
+                    "double-nesting (struct/tuple in struct/tuple) not possible",
+                )),
+            },
             _ => {
                 self.cols_treat = MCD::Done;
                 visitor.visit_seq(FieldsSeqVisitor::new(&mut self))
@@ -215,13 +424,30 @@ where
         }
     }
 
-    fn deserialize_map<V>(self, _visitor: V) -> DeserializationResult<V::Value>
+    // The column names are used as keys; a nested map is filled from the columns
+    // with the field name as prefix, and the rest of the column names as keys.
+    fn deserialize_map<V>(self, visitor: V) -> DeserializationResult<V::Value>
     where
         V: serde::de::Visitor<'x>,
     {
-        Err(DeserializationError::NotImplemented(
-            "RowDeserializer::deserialize_map()",
-        ))
+        trace!("RowDeserializer::deserialize_map()");
+        let columns = match self.cols_treat {
+            MCD::Done => match self.nested_prefix.take() {
+                Some(prefix) => Columns::Prefixed(prefix),
+                None => {
+                    return Err(impl_err(
+                        "nested map requires column names with the field name as prefix",
+                    ));
+                }
+            },
+            _ => {
+                self.cols_treat = MCD::Done;
+                Columns::All
+            }
+        };
+        let mut map_visitor = FieldsMapVisitor::new(self, &[], columns);
+        map_visitor.is_map = true;
+        visitor.visit_map(map_visitor)
     }
 
     fn deserialize_unit_struct<V>(
@@ -266,23 +492,35 @@ where
         ))
     }
 
+    // A nested struct is filled from the columns whose names start with the field name
+    // and a nesting separator; a struct in a tuple is filled from the columns determined
+    // by element_columns().
     fn deserialize_struct<V>(
-        mut self,
+        self,
         _name: &'static str,
-        _fields: &'static [&'static str],
+        fields: &'static [&'static str],
         visitor: V,
     ) -> DeserializationResult<V::Value>
     where
         V: serde::de::Visitor<'x>,
     {
         trace!("RowDeserializer::deserialize_struct()");
-        match self.cols_treat {
-            MCD::Done => Err(impl_err("double-nesting (struct in struct) not possible")),
+        let columns = match self.cols_treat {
+            MCD::Done => match (self.nested_prefix.take(), self.tuple_element.take()) {
+                (Some(prefix), _) => Columns::Prefixed(prefix),
+                (None, Some(element)) => self.element_columns(element, fields),
+                (None, None) => {
+                    return Err(impl_err(
+                        "nested struct requires column names with the field name as prefix",
+                    ));
+                }
+            },
             _ => {
                 self.cols_treat = MCD::Done;
-                visitor.visit_map(FieldsMapVisitor::new(&mut self))
+                Columns::All
             }
-        }
+        };
+        visitor.visit_map(FieldsMapVisitor::new(self, fields, columns))
     }
 
     fn deserialize_bytes<V>(self, visitor: V) -> DeserializationResult<V::Value>
@@ -290,7 +528,7 @@ where
         V: serde::de::Visitor<'x>,
     {
         trace!("RowDeserializer::deserialize_bytes()");
-        visitor.visit_bytes(&DbValueInto::<Vec<u8>>::try_into(self.next_value()?)?)
+        self.next_field()?.deserialize_bytes(visitor)
     }
 
     fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -298,7 +536,7 @@ where
         V: serde::de::Visitor<'x>,
     {
         trace!("RowDeserializer::deserialize_byte_buf()");
-        visitor.visit_bytes(&DbValueInto::<Vec<u8>>::try_into(self.next_value()?)?)
+        self.next_field()?.deserialize_byte_buf(visitor)
     }
 
     fn deserialize_tuple<V>(mut self, _len: usize, visitor: V) -> DeserializationResult<V::Value>
@@ -319,16 +557,15 @@ where
 
     fn deserialize_enum<V>(
         self,
-        _name: &'static str,
-        _variants: &'static [&'static str],
-        _visitor: V,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: serde::de::Visitor<'x>,
     {
-        Err(DeserializationError::NotImplemented(
-            "RowDeserializer::deserialize_enum()",
-        ))
+        trace!("RowDeserializer::deserialize_enum() with name = {}", name);
+        self.next_field()?.deserialize_enum(name, variants, visitor)
     }
 
     fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -341,14 +578,14 @@ where
             )),
             curr_len => {
                 let idx = self.row.number_of_fields() - curr_len;
-                match self.get_fieldname(idx) {
+                match self.matching_name(idx) {
                     Some(fieldname) => {
                         trace!(
                             "RowDeserializer::deserialize_identifier(): column {:?} ({})",
                             idx,
                             fieldname
                         );
-                        visitor.visit_str(fieldname)
+                        visitor.visit_str(&fieldname)
                     }
                     None => Err(impl_err(
                         "no fieldname in RowDeserializer::deserialize_identifier()",
@@ -358,11 +595,15 @@ where
         }
     }
 
-    fn deserialize_ignored_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: serde::de::Visitor<'x>,
     {
         trace!("RowDeserializer::deserialize_ignored_any()");
+        if self.options.ignore_unknown_columns {
+            self.next_value()?;
+            return visitor.visit_unit();
+        }
         let fieldname = self
             .get_fieldname(self.row.number_of_fields() - self.row.len())
             .cloned()
@@ -371,26 +612,75 @@ where
     }
 }
 
-struct FieldsMapVisitor<'a, R: 'a + DeserializableRow>
+struct FieldsMapVisitor<'a, 'o: 'a, R: 'a + DeserializableRow>
 where
     <R as DeserializableRow>::V: DbValue,
 {
-    de: &'a mut RowDeserializer<R>,
+    de: &'a mut RowDeserializer<'o, R>,
+    fields: &'static [&'static str],
+    columns: Columns,
+    seen: Vec<&'static str>,
+    // all columns are taken as keys, rather than only those that match a field
+    is_map: bool,
 }
 
-impl<'a, R: DeserializableRow> FieldsMapVisitor<'a, R>
+impl<'a, 'o, R: DeserializableRow> FieldsMapVisitor<'a, 'o, R>
 where
     <R as DeserializableRow>::V: DbValue,
 {
-    pub fn new(de: &'a mut RowDeserializer<R>) -> Self {
+    pub fn new(
+        de: &'a mut RowDeserializer<'o, R>,
+        fields: &'static [&'static str],
+        columns: Columns,
+    ) -> Self {
         trace!("FieldsMapVisitor::new()");
-        FieldsMapVisitor { de }
+        FieldsMapVisitor {
+            de,
+            fields,
+            columns,
+            seen: Vec::new(),
+            is_map: false,
+        }
+    }
+
+    // Returns the field of a nested struct to which the column name belongs,
+    // and the length of the column name prefix that belongs to the field.
+    // Fields that are filled from a column with exactly their name are no nested structs.
+    fn nested_field(&self, name: &str) -> Option<(&'static str, usize)> {
+        let separators = &self.de.options.nesting_separators;
+        self.fields
+            .iter()
+            .filter(|field| !self.has_column(field))
+            .filter_map(|field| {
+                separators
+                    .iter()
+                    .find(|sep| {
+                        name.len() > field.len() + sep.len()
+                            && self.de.options.column_name_matching.has_prefix(name, field)
+                            && name[field.len()..].starts_with(sep.as_str())
+                    })
+                    .map(|sep| (*field, field.len() + sep.len()))
+            })
+            .max_by_key(|&(field, _)| field.len())
+    }
+
+    // Checks if a column of the row, within the columns of this struct, has the field name.
+    fn has_column(&self, field: &str) -> bool {
+        let matching = &self.de.options.column_name_matching;
+        (0..self.de.row.number_of_fields())
+            .filter_map(|idx| self.de.matching_name(idx))
+            .any(|name| match self.columns {
+                Columns::Prefixed(ref prefix) => {
+                    matching.has_prefix(&name, prefix) && matching.eq(field, &name[prefix.len()..])
+                }
+                Columns::All | Columns::Fields => matching.eq(field, &name),
+            })
     }
 }
 
-impl<'x, 'a, R: DeserializableRow> serde::de::MapAccess<'x> for FieldsMapVisitor<'a, R>
+impl<'x, 'a, 'o, R: DeserializableRow> serde::de::MapAccess<'x> for FieldsMapVisitor<'a, 'o, R>
 where
-    <R as DeserializableRow>::V: DbValue,
+    <R as DeserializableRow>::V: DbValue + 'x,
 {
     type Error = DeserializationError;
 
@@ -398,24 +688,74 @@ where
     where
         K: serde::de::DeserializeSeed<'x>,
     {
-        match self.de.row.len() {
-            0 => {
-                trace!("FieldsMapVisitor::next_key_seed() on empty row");
-                Ok(None)
-            }
-            len => {
-                let idx = self.de.row.number_of_fields() - len;
-                trace!("FieldsMapVisitor::next_key_seed() for col {}", idx);
-                let value = seed.deserialize(&mut *self.de);
-                match value {
-                    Ok(res) => Ok(Some(res)),
-                    Err(_) => {
-                        let fname = self.de.get_fieldname(idx).unwrap();
-                        trace!("FieldsMapVisitor::next_key_seed(): Error at {}", fname);
-                        Err(DeserializationError::UnknownField(fname.clone()))
+        let options = self.de.options;
+        let matching = &options.column_name_matching;
+        let (fname, key) = loop {
+            let (fname, normalized) = match self.de.row.len() {
+                0 => {
+                    trace!("FieldsMapVisitor::next_key_seed() on empty row");
+                    if let Columns::All = self.columns {
+                        self.de.report_ignored_columns();
+                    }
+                    return Ok(None);
+                }
+                len => {
+                    let idx = self.de.row.number_of_fields() - len;
+                    trace!("FieldsMapVisitor::next_key_seed() for col {}", idx);
+                    match (self.de.get_fieldname(idx), self.de.matching_name(idx)) {
+                        (Some(fname), Some(normalized)) => {
+                            (fname.clone(), normalized.into_owned())
+                        }
+                        _ => return Err(impl_err("no fieldname in FieldsMapVisitor")),
+                    }
+                }
+            };
+
+            let prefix_len = match self.columns {
+                Columns::Prefixed(ref prefix) if matching.has_prefix(&normalized, prefix) => {
+                    prefix.len()
+                }
+                Columns::Prefixed(_) => return Ok(None),
+                Columns::All | Columns::Fields => 0,
+            };
+            let name = &normalized[prefix_len..];
+            let found = self.fields.iter().find(|f| matching.eq(f, name));
+            let (key, nested_prefix) = match found {
+                Some(field) => (Some(*field), None),
+                None => match self.nested_field(name) {
+                    Some((field, len)) => {
+                        (Some(field), Some(normalized[..prefix_len + len].to_string()))
                     }
+                    None => (None, None),
+                },
+            };
+            if let Columns::Fields = self.columns {
+                match key {
+                    Some(field) if !self.seen.contains(&field) => self.seen.push(field),
+                    _ => return Ok(None),
+                }
+            }
+
+            match key {
+                None if !self.is_map && options.ignore_unknown_columns => {
+                    trace!("FieldsMapVisitor::next_key_seed(): ignoring column {}", fname);
+                    self.de.row.next();
+                    self.de.ignored_columns.push(fname);
+                }
+                _ => {
+                    self.de.nested_prefix = nested_prefix;
+                    let key = key.map_or_else(|| name.to_string(), ToString::to_string);
+                    break (fname, key);
                 }
             }
+        };
+
+        match seed.deserialize(IntoDeserializer::<DeserializationError>::into_deserializer(key)) {
+            Ok(res) => Ok(Some(res)),
+            Err(_) => {
+                trace!("FieldsMapVisitor::next_key_seed(): Error at {}", fname);
+                Err(DeserializationError::UnknownField(fname))
+            }
         }
     }
 
@@ -428,11 +768,13 @@ where
                 "FieldsMapVisitor::next_value_seed(): no more value",
             )),
             len => {
-                trace!(
-                    "FieldsMapVisitor::next_value_seed() for col {}",
-                    self.de.row.number_of_fields() - len
-                );
-                seed.deserialize(&mut *self.de)
+                let idx = self.de.row.number_of_fields() - len;
+                trace!("FieldsMapVisitor::next_value_seed() for col {}", idx);
+                let result = seed
+                    .deserialize(&mut *self.de)
+                    .map_err(|e| e.with_column(idx, self.de.get_fieldname(idx)));
+                self.de.nested_prefix = None;
+                result
             }
         }
     }
@@ -442,26 +784,27 @@ fn impl_err(s: &'static str) -> DeserializationError {
     DeserializationError::Usage(s.to_string())
 }
 
-struct FieldsSeqVisitor<'a, R: 'a + DeserializableRow>
+struct FieldsSeqVisitor<'a, 'o: 'a, R: 'a + DeserializableRow>
 where
     <R as DeserializableRow>::V: DbValue,
 {
-    de: &'a mut RowDeserializer<R>,
+    de: &'a mut RowDeserializer<'o, R>,
+    element: usize,
 }
-impl<'a, R: DeserializableRow> FieldsSeqVisitor<'a, R>
+impl<'a, 'o, R: DeserializableRow> FieldsSeqVisitor<'a, 'o, R>
 where
     <R as DeserializableRow>::V: DbValue,
 {
-    pub fn new(de: &'a mut RowDeserializer<R>) -> Self {
+    pub fn new(de: &'a mut RowDeserializer<'o, R>) -> Self {
         trace!("FieldsSeqVisitor::new()");
-        FieldsSeqVisitor { de }
+        FieldsSeqVisitor { de, element: 0 }
     }
 }
 
-impl<'x, 'a, R> serde::de::SeqAccess<'x> for FieldsSeqVisitor<'a, R>
+impl<'x, 'a, 'o, R> serde::de::SeqAccess<'x> for FieldsSeqVisitor<'a, 'o, R>
 where
     R: DeserializableRow,
-    <R as DeserializableRow>::V: DbValue,
+    <R as DeserializableRow>::V: DbValue + 'x,
 {
     type Error = DeserializationError;
 
@@ -470,9 +813,83 @@ where
         T: serde::de::DeserializeSeed<'x>,
     {
         trace!("FieldsSeqVisitor.next_element_seed()");
-        match self.de.row.next() {
+        if self.de.row.len() == 0 {
+            return Ok(None);
+        }
+        let idx = self.de.row.number_of_fields() - self.de.row.len();
+        self.de.tuple_element = Some(self.element);
+        self.element += 1;
+        let result = seed
+            .deserialize(&mut *self.de)
+            .map(Some)
+            .map_err(|e| e.with_column(idx, self.de.get_fieldname(idx)));
+        self.de.tuple_element = None;
+        result
+    }
+}
+
+// Provides the elements of a nested Vec, one from each row of the group.
+struct GroupSeqVisitor<'a, 'o: 'a, R: 'a + DeserializableRow>
+where
+    <R as DeserializableRow>::V: DbValue,
+{
+    de: &'a mut RowDeserializer<'o, R>,
+    prefix: String,
+    first: bool,
+}
+impl<'a, 'o, R: DeserializableRow> GroupSeqVisitor<'a, 'o, R>
+where
+    <R as DeserializableRow>::V: DbValue,
+{
+    pub fn new(de: &'a mut RowDeserializer<'o, R>, prefix: String) -> Self {
+        trace!("GroupSeqVisitor::new()");
+        GroupSeqVisitor {
+            de,
+            prefix,
+            first: true,
+        }
+    }
+}
+
+impl<'x, 'a, 'o, R> serde::de::SeqAccess<'x> for GroupSeqVisitor<'a, 'o, R>
+where
+    R: DeserializableRow,
+    <R as DeserializableRow>::V: DbValue + 'x,
+{
+    type Error = DeserializationError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: serde::de::DeserializeSeed<'x>,
+    {
+        trace!("GroupSeqVisitor.next_element_seed()");
+        if self.first {
+            self.first = false;
+            self.de.nested_prefix = Some(self.prefix.clone());
+            return seed.deserialize(&mut *self.de).map(Some);
+        }
+        match self.de.following_rows.pop_front() {
             None => Ok(None),
-            Some(val) => seed.deserialize(FieldDeserializer::new(val)).map(Some),
+            Some((row_idx, row)) => {
+                let options = self.de.options;
+                let matching = &options.column_name_matching;
+                let mut rd = RowDeserializer::new(row, options);
+                rd.cols_treat = MCD::Done;
+                loop {
+                    let skip = match rd.current_matching_name() {
+                        Some(name) => !matching.has_prefix(&name, &self.prefix),
+                        None => false,
+                    };
+                    if !skip {
+                        break;
+                    }
+                    rd.row.next();
+                }
+                rd.nested_prefix = Some(self.prefix.clone());
+                seed.deserialize(&mut rd)
+                    .map(Some)
+                    .map_err(|e| e.with_row(row_idx))
+            }
         }
     }
 }
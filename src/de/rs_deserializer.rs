@@ -1,11 +1,16 @@
 use log::trace;
 use serde;
 use serde::de::Deserialize as SD;
+use std::collections::VecDeque;
+use std::mem;
+use std::rc::Rc;
 
+use crate::de::buffered_row::BufferedRow;
+use crate::de::columns_deserializer::ColumnsDeserializer;
 use crate::de::row_deserializer::RowDeserializer;
 use crate::de::{
     DbValue, DeserializableResultset, DeserializableRow, DeserializationError,
-    DeserializationResult,
+    DeserializationOptions, DeserializationResult, RowCardinality,
 };
 
 enum MCD {
@@ -15,47 +20,152 @@ enum MCD {
 }
 
 // Deserialize a ResultSet into a normal rust type.
-pub struct RsDeserializer<RS> {
+pub struct RsDeserializer<'o, RS> {
     rs: RS,
     rows_treat: MCD,
+    options: &'o DeserializationOptions,
 }
 
-impl<RS> RsDeserializer<RS>
+impl<'o, RS> RsDeserializer<'o, RS>
 where
     RS: DeserializableResultset,
     <<RS as DeserializableResultset>::ROW as DeserializableRow>::V: DbValue,
 {
-    pub fn try_new(mut rs: RS) -> Result<RsDeserializer<RS>, DeserializationError> {
+    pub fn try_new(
+        mut rs: RS,
+        options: &'o DeserializationOptions,
+    ) -> Result<RsDeserializer<'o, RS>, DeserializationError> {
         trace!("RsDeserializer::new()");
         let rows_treat = if rs.has_multiple_rows()? {
             MCD::Must
         } else {
             MCD::Can
         };
-        Ok(RsDeserializer { rows_treat, rs })
+        Ok(RsDeserializer {
+            rows_treat,
+            rs,
+            options,
+        })
     }
 
     fn pop_single_row(&mut self) -> DeserializationResult<<RS as DeserializableResultset>::ROW> {
-        self.single_row_deserialization_allowed()?;
-        match self.rs.next()? {
-            None => Err(DeserializationError::Usage(String::from(
-                "no row found in resultset",
-            ))),
+        match self.pop_optional_row()? {
+            None => Err(no_row()),
             Some(row) => Ok(row),
         }
     }
 
+    // Returns None for an empty resultset, if the cardinality allows it.
+    fn pop_optional_row(
+        &mut self,
+    ) -> DeserializationResult<Option<<RS as DeserializableResultset>::ROW>> {
+        self.single_row_deserialization_allowed()?;
+        match (self.rs.next()?, self.options.cardinality) {
+            (None, RowCardinality::ExactlyOne) => Err(no_row()),
+            (row, _) => Ok(row),
+        }
+    }
+
     fn single_row_deserialization_allowed(&self) -> DeserializationResult<()> {
-        match self.rows_treat {
-            MCD::Must => Err(DeserializationError::TrailingRows),
+        match (&self.rows_treat, self.options.cardinality) {
+            (MCD::Must, RowCardinality::ExactlyOne) | (MCD::Must, RowCardinality::AtMostOne) => {
+                Err(DeserializationError::TrailingRows)
+            }
             _ => Ok(()),
         }
     }
+
+    // Deserializes each group of consecutive rows with equal values in the key columns
+    // into one instance of T.
+    pub fn into_groups<'de, T, V>(mut self, key_columns: &[&str]) -> DeserializationResult<Vec<T>>
+    where
+        T: serde::de::Deserialize<'de>,
+        RS::ROW: DeserializableRow<V = V>,
+        V: DbValue + PartialEq + 'de,
+    {
+        trace!("RsDeserializer::into_groups()");
+        let fieldnames: Rc<Vec<String>> = Rc::new(
+            (0..self.rs.number_of_fields())
+                .map(|idx| self.rs.fieldname(idx).cloned().unwrap_or_default())
+                .collect(),
+        );
+        let matching = &self.options.column_name_matching;
+        let key_indices = key_columns
+            .iter()
+            .map(|key| {
+                fieldnames
+                    .iter()
+                    .position(|name| matching.eq(key, &matching.normalize(name)))
+                    .ok_or_else(|| {
+                        DeserializationError::Usage(format!("key column {} not found", key))
+                    })
+            })
+            .collect::<DeserializationResult<Vec<usize>>>()?;
+
+        let mut result = Vec::new();
+        let mut group = VecDeque::<(usize, BufferedRow<V>)>::new();
+        let mut row_idx = 0;
+        while let Some(row) = self.rs.next()? {
+            let row = BufferedRow::new(row, Rc::clone(&fieldnames));
+            let same_key = match group.front() {
+                Some((_, first)) => {
+                    key_indices.iter().all(|idx| first.value(*idx) == row.value(*idx))
+                }
+                None => true,
+            };
+            if !same_key {
+                result.push(self.deserialize_group(mem::take(&mut group))?);
+            }
+            group.push_back((row_idx, row));
+            row_idx += 1;
+        }
+        if !group.is_empty() {
+            result.push(self.deserialize_group(group)?);
+        }
+        Ok(result)
+    }
+
+    // Deserializes the resultset column-wise into a struct or a map of sequences.
+    pub fn into_columns<'de, T>(mut self) -> DeserializationResult<T>
+    where
+        T: serde::de::Deserialize<'de>,
+        <RS::ROW as DeserializableRow>::V: 'de,
+    {
+        trace!("RsDeserializer::into_columns()");
+        let mut columns: Vec<(String, Vec<_>)> = (0..self.rs.number_of_fields())
+            .map(|idx| (self.rs.fieldname(idx).cloned().unwrap_or_default(), Vec::new()))
+            .collect();
+        while let Some(mut row) = self.rs.next()? {
+            for (_, values) in &mut columns {
+                values.extend(row.next());
+            }
+        }
+        SD::deserialize(ColumnsDeserializer::new(columns, self.options))
+    }
+
+    fn deserialize_group<'de, T, V>(
+        &self,
+        mut group: VecDeque<(usize, BufferedRow<V>)>,
+    ) -> DeserializationResult<T>
+    where
+        T: serde::de::Deserialize<'de>,
+        V: DbValue + 'de,
+    {
+        let (row_idx, row) = group.pop_front().unwrap(/*never empty*/);
+        let rd = RowDeserializer::new_group(row, group, self.options);
+        let mut rd = if row_idx == 0 { rd } else { rd.without_report() };
+        SD::deserialize(&mut rd).map_err(|e| e.with_row(row_idx))
+    }
 }
 
-impl<'x, 'a, RS: DeserializableResultset> serde::Deserializer<'x> for &'a mut RsDeserializer<RS>
+fn no_row() -> DeserializationError {
+    DeserializationError::Usage(String::from("no row found in resultset"))
+}
+
+impl<'x, 'a, 'o, RS: DeserializableResultset> serde::Deserializer<'x>
+    for &'a mut RsDeserializer<'o, RS>
 where
-    <<RS as DeserializableResultset>::ROW as DeserializableRow>::V: DbValue,
+    <<RS as DeserializableResultset>::ROW as DeserializableRow>::V: DbValue + 'x,
 {
     type Error = DeserializationError;
 
@@ -65,6 +175,7 @@ where
     {
         visitor.visit_string(SD::deserialize(&mut RowDeserializer::new(
             self.pop_single_row()?,
+            self.options,
         ))?)
     }
 
@@ -75,6 +186,7 @@ where
         trace!("RsDeserializer::deserialize_bool()");
         visitor.visit_bool(SD::deserialize(&mut RowDeserializer::new(
             self.pop_single_row()?,
+            self.options,
         ))?)
     }
 
@@ -85,6 +197,7 @@ where
         trace!("RsDeserializer::deserialize_u8()");
         visitor.visit_u8(SD::deserialize(&mut RowDeserializer::new(
             self.pop_single_row()?,
+            self.options,
         ))?)
     }
 
@@ -95,6 +208,7 @@ where
         trace!("RsDeserializer::deserialize_u16()");
         visitor.visit_u16(SD::deserialize(&mut RowDeserializer::new(
             self.pop_single_row()?,
+            self.options,
         ))?)
     }
 
@@ -105,6 +219,7 @@ where
         trace!("RsDeserializer::deserialize_u32()");
         visitor.visit_u32(SD::deserialize(&mut RowDeserializer::new(
             self.pop_single_row()?,
+            self.options,
         ))?)
     }
 
@@ -115,6 +230,18 @@ where
         trace!("RsDeserializer::deserialize_u64()");
         visitor.visit_u64(SD::deserialize(&mut RowDeserializer::new(
             self.pop_single_row()?,
+            self.options,
+        ))?)
+    }
+
+    fn deserialize_u128<V>(self, visitor: V) -> DeserializationResult<V::Value>
+    where
+        V: serde::de::Visitor<'x>,
+    {
+        trace!("RsDeserializer::deserialize_u128()");
+        visitor.visit_u128(SD::deserialize(&mut RowDeserializer::new(
+            self.pop_single_row()?,
+            self.options,
         ))?)
     }
 
@@ -125,6 +252,7 @@ where
         trace!("RsDeserializer::deserialize_i8()");
         visitor.visit_i8(SD::deserialize(&mut RowDeserializer::new(
             self.pop_single_row()?,
+            self.options,
         ))?)
     }
 
@@ -135,6 +263,7 @@ where
         trace!("RsDeserializer::deserialize_i16()");
         visitor.visit_i16(SD::deserialize(&mut RowDeserializer::new(
             self.pop_single_row()?,
+            self.options,
         ))?)
     }
 
@@ -145,6 +274,7 @@ where
         trace!("RsDeserializer::deserialize_i32()");
         visitor.visit_i32(SD::deserialize(&mut RowDeserializer::new(
             self.pop_single_row()?,
+            self.options,
         ))?)
     }
 
@@ -155,6 +285,18 @@ where
         trace!("RsDeserializer::deserialize_i64()");
         visitor.visit_i64(SD::deserialize(&mut RowDeserializer::new(
             self.pop_single_row()?,
+            self.options,
+        ))?)
+    }
+
+    fn deserialize_i128<V>(self, visitor: V) -> DeserializationResult<V::Value>
+    where
+        V: serde::de::Visitor<'x>,
+    {
+        trace!("RsDeserializer::deserialize_i128()");
+        visitor.visit_i128(SD::deserialize(&mut RowDeserializer::new(
+            self.pop_single_row()?,
+            self.options,
         ))?)
     }
 
@@ -165,6 +307,7 @@ where
         trace!("RsDeserializer::deserialize_f32()");
         visitor.visit_f32(SD::deserialize(&mut RowDeserializer::new(
             self.pop_single_row()?,
+            self.options,
         ))?)
     }
 
@@ -175,6 +318,7 @@ where
         trace!("RsDeserializer::deserialize_f64()");
         visitor.visit_f64(SD::deserialize(&mut RowDeserializer::new(
             self.pop_single_row()?,
+            self.options,
         ))?)
     }
 
@@ -191,8 +335,9 @@ where
     where
         V: serde::de::Visitor<'x>,
     {
-        trace!("RsDeserializer::deserialize_str(), delegates to deserialize_string()");
-        self.deserialize_string(visitor)
+        trace!("RsDeserializer::deserialize_str()");
+        let mut rd = RowDeserializer::new(self.pop_single_row()?, self.options);
+        rd.deserialize_str(visitor)
     }
 
     fn deserialize_string<V>(self, visitor: V) -> DeserializationResult<V::Value>
@@ -202,25 +347,41 @@ where
         trace!("RsDeserializer::deserialize_string()");
         visitor.visit_string(SD::deserialize(&mut RowDeserializer::new(
             self.pop_single_row()?,
+            self.options,
         ))?)
     }
 
-    fn deserialize_unit<V>(self, _visitor: V) -> DeserializationResult<V::Value>
+    // Only an empty resultset can be converted into ().
+    fn deserialize_unit<V>(self, visitor: V) -> DeserializationResult<V::Value>
     where
         V: serde::de::Visitor<'x>,
     {
-        Err(DeserializationError::NotImplemented(
-            "RsDeserializer::deserialize_unit()",
-        ))
+        trace!("RsDeserializer::deserialize_unit()");
+        match self.rs.next()? {
+            None => visitor.visit_unit(),
+            Some(_) => Err(DeserializationError::TrailingRows),
+        }
     }
 
+    // An empty resultset is converted into None;
+    // a single row with a single column is converted into None if the value is NULL.
     fn deserialize_option<V>(self, visitor: V) -> DeserializationResult<V::Value>
     where
         V: serde::de::Visitor<'x>,
     {
         trace!("RsDeserializer::deserialize_option()");
-        let mut rd = RowDeserializer::new(self.pop_single_row()?);
-        rd.deserialize_option(visitor)
+        match self.pop_optional_row()? {
+            None => visitor.visit_none(),
+            Some(row) => {
+                let single_value = row.len() == 1;
+                let mut rd = RowDeserializer::new(row, self.options);
+                if single_value {
+                    rd.deserialize_option(visitor)
+                } else {
+                    visitor.visit_some(&mut rd)
+                }
+            }
+        }
     }
 
     fn deserialize_seq<V>(mut self, visitor: V) -> DeserializationResult<V::Value>
@@ -239,13 +400,24 @@ where
         }
     }
 
-    fn deserialize_map<V>(self, _visitor: V) -> DeserializationResult<V::Value>
+    fn deserialize_map<V>(mut self, visitor: V) -> DeserializationResult<V::Value>
     where
         V: serde::de::Visitor<'x>,
     {
-        Err(DeserializationError::NotImplemented(
-            "RsDeserializer::deserialize_map()",
-        ))
+        trace!("RsDeserializer::deserialize_map()");
+        match (&self.rows_treat, self.rs.number_of_fields()) {
+            (MCD::Done, _) => Err(DeserializationError::Usage(
+                "deserialize_map() when rows_treat = MCD::Done".to_string(),
+            )),
+            (_, 0) | (_, 1) => Err(DeserializationError::Usage(
+                "a map can only be filled from a resultset with at least two columns"
+                    .to_string(),
+            )),
+            _ => {
+                self.rows_treat = MCD::Done;
+                Ok(visitor.visit_map(RowsMapVisitor::new(&mut self))?)
+            }
+        }
     }
 
     fn deserialize_unit_struct<V>(
@@ -289,7 +461,7 @@ where
             "RsDeserializer::deserialize_tuple_struct() with name = {}",
             name
         );
-        let mut rd = RowDeserializer::new(self.pop_single_row()?);
+        let mut rd = RowDeserializer::new(self.pop_single_row()?, self.options);
         rd.deserialize_tuple_struct(name, len, visitor)
     }
 
@@ -303,7 +475,7 @@ where
         V: serde::de::Visitor<'x>,
     {
         trace!("RsDeserializer::deserialize_struct() with name = {}", name);
-        let mut rd = RowDeserializer::new(self.pop_single_row()?);
+        let mut rd = RowDeserializer::new(self.pop_single_row()?, self.options);
         rd.deserialize_struct(name, fields, visitor)
     }
 
@@ -312,7 +484,7 @@ where
         V: serde::de::Visitor<'x>,
     {
         trace!("RsDeserializer::deserialize_bytes()");
-        let mut rd = RowDeserializer::new(self.pop_single_row()?);
+        let mut rd = RowDeserializer::new(self.pop_single_row()?, self.options);
         rd.deserialize_bytes(visitor)
     }
 
@@ -321,7 +493,7 @@ where
         V: serde::de::Visitor<'x>,
     {
         trace!("RsDeserializer::deserialize_byte_buf()");
-        let mut rd = RowDeserializer::new(self.pop_single_row()?);
+        let mut rd = RowDeserializer::new(self.pop_single_row()?, self.options);
         rd.deserialize_byte_buf(visitor)
     }
 
@@ -330,22 +502,22 @@ where
         V: serde::de::Visitor<'x>,
     {
         trace!("RsDeserializer::deserialize_tuple()");
-        let mut rd = RowDeserializer::new(self.pop_single_row()?);
+        let mut rd = RowDeserializer::new(self.pop_single_row()?, self.options);
         rd.deserialize_tuple(len, visitor)
     }
 
     fn deserialize_enum<V>(
         self,
-        _name: &'static str,
-        _variants: &'static [&'static str],
-        _visitor: V,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: serde::de::Visitor<'x>,
     {
-        Err(DeserializationError::NotImplemented(
-            "RsDeserializer::deserialize_enum()",
-        ))
+        trace!("RsDeserializer::deserialize_enum() with name = {}", name);
+        let mut rd = RowDeserializer::new(self.pop_single_row()?, self.options);
+        rd.deserialize_enum(name, variants, visitor)
     }
 
     fn deserialize_identifier<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
@@ -368,18 +540,23 @@ where
 }
 
 // we use generalization <R> here because this allows us to bind the parameter to the lifetime 'a
-struct RowsVisitor<'a, R: 'a> {
-    de: &'a mut RsDeserializer<R>,
+struct RowsVisitor<'a, 'o: 'a, R: 'a> {
+    de: &'a mut RsDeserializer<'o, R>,
+    row_idx: usize,
 }
 
-impl<'a, R> RowsVisitor<'a, R> {
-    pub fn new(de: &'a mut RsDeserializer<R>) -> Self {
+impl<'a, 'o, R> RowsVisitor<'a, 'o, R> {
+    pub fn new(de: &'a mut RsDeserializer<'o, R>) -> Self {
         trace!("RowsVisitor::new()");
-        RowsVisitor { de }
+        RowsVisitor { de, row_idx: 0 }
     }
 }
 
-impl<'x, 'a, R: DeserializableResultset> serde::de::SeqAccess<'x> for RowsVisitor<'a, R> {
+impl<'x, 'a, 'o, R: DeserializableResultset> serde::de::SeqAccess<'x>
+    for RowsVisitor<'a, 'o, R>
+where
+    <<R as DeserializableResultset>::ROW as DeserializableRow>::V: 'x,
+{
     type Error = DeserializationError;
 
     fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
@@ -389,7 +566,84 @@ impl<'x, 'a, R: DeserializableResultset> serde::de::SeqAccess<'x> for RowsVisito
         trace!("RowsVisitor.next_element_seed()");
         match self.de.rs.next()? {
             None => Ok(None),
-            Some(row) => seed.deserialize(&mut RowDeserializer::new(row)).map(Some),
+            Some(row) => {
+                let row_idx = self.row_idx;
+                self.row_idx += 1;
+                let rd = RowDeserializer::new(row, self.de.options);
+                let mut rd = if row_idx == 0 { rd } else { rd.without_report() };
+                seed.deserialize(&mut rd)
+                    .map(Some)
+                    .map_err(|e| e.with_row(row_idx))
+            }
+        }
+    }
+}
+
+// Provides each row as a map entry, with the first column as key,
+// and the remaining columns as value.
+struct RowsMapVisitor<'a, 'o: 'a, R: DeserializableResultset + 'a> {
+    de: &'a mut RsDeserializer<'o, R>,
+    row_idx: usize,
+    current: Option<RowDeserializer<'o, R::ROW>>,
+}
+
+impl<'a, 'o, R: DeserializableResultset> RowsMapVisitor<'a, 'o, R> {
+    pub fn new(de: &'a mut RsDeserializer<'o, R>) -> Self {
+        trace!("RowsMapVisitor::new()");
+        RowsMapVisitor {
+            de,
+            row_idx: 0,
+            current: None,
+        }
+    }
+}
+
+impl<'x, 'a, 'o, R: DeserializableResultset> serde::de::MapAccess<'x>
+    for RowsMapVisitor<'a, 'o, R>
+where
+    <<R as DeserializableResultset>::ROW as DeserializableRow>::V: DbValue + 'x,
+{
+    type Error = DeserializationError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: serde::de::DeserializeSeed<'x>,
+    {
+        trace!("RowsMapVisitor.next_key_seed()");
+        match self.de.rs.next()? {
+            None => Ok(None),
+            Some(row) => {
+                let row_idx = self.row_idx;
+                let rd = RowDeserializer::new_map_entry(row, self.de.options);
+                let mut rd = if row_idx == 0 { rd } else { rd.without_report() };
+                let key = seed.deserialize(&mut rd).map_err(|e| {
+                    e.with_column(0, self.de.rs.fieldname(0)).with_row(row_idx)
+                })?;
+                rd.treat_remaining_columns_as_row();
+                self.current = Some(rd);
+                Ok(Some(key))
+            }
         }
     }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::DeserializeSeed<'x>,
+    {
+        trace!("RowsMapVisitor.next_value_seed()");
+        let row_idx = self.row_idx;
+        self.row_idx += 1;
+        let mut rd = self.current.take().ok_or_else(|| {
+            DeserializationError::Usage("next_value_seed() without key".to_string())
+        })?;
+        let number_of_fields = self.de.rs.number_of_fields();
+        seed.deserialize(&mut rd).map_err(|e| {
+            let e = if number_of_fields == 2 {
+                e.with_column(1, self.de.rs.fieldname(1))
+            } else {
+                e
+            };
+            e.with_row(row_idx)
+        })
+    }
 }
@@ -0,0 +1,100 @@
+use log::trace;
+use serde;
+use std::marker::PhantomData;
+
+use crate::de::row_deserializer::RowDeserializer;
+use crate::de::{
+    DbValue, DeserializableResultset, DeserializableRow, DeserializationError,
+    DeserializationOptions, DeserializationResult,
+};
+
+/// Iterator over the rows of a resultset, which converts each row into a rust value.
+///
+/// Is returned by
+/// [`DeserializableResultset::into_typed_iter()`](trait.DeserializableResultset.html#method.into_typed_iter)
+/// and by `DeserializableResultset::into_typed_iter_with()`.
+/// The rows are fetched lazily from the resultset, one with each call to `next()`.
+pub struct TypedIter<RS, T> {
+    rs: RS,
+    conversion: RowConversion,
+    target: PhantomData<fn() -> T>,
+}
+
+impl<RS, T> TypedIter<RS, T> {
+    pub(crate) fn new(rs: RS, options: DeserializationOptions) -> TypedIter<RS, T> {
+        trace!("TypedIter::new()");
+        TypedIter {
+            rs,
+            conversion: RowConversion::new(options),
+            target: PhantomData,
+        }
+    }
+}
+
+impl<RS, T> Iterator for TypedIter<RS, T>
+where
+    RS: DeserializableResultset,
+    T: serde::de::DeserializeOwned,
+{
+    type Item = Result<T, RS::E>;
+
+    fn next(&mut self) -> Option<Result<T, RS::E>> {
+        if self.conversion.done {
+            return None;
+        }
+        let fetched = self.rs.next();
+        self.conversion.convert(fetched)
+    }
+}
+
+// Converts the rows that are fetched one by one, for TypedIter and TypedStream.
+pub(crate) struct RowConversion {
+    options: DeserializationOptions,
+    row_idx: usize,
+    // no further row is fetched after the last row or after a failed fetch
+    pub(crate) done: bool,
+}
+
+impl RowConversion {
+    pub(crate) fn new(options: DeserializationOptions) -> RowConversion {
+        RowConversion {
+            options,
+            row_idx: 0,
+            done: false,
+        }
+    }
+
+    // Converts the result of fetching the next row into the item of the iterator or stream.
+    pub(crate) fn convert<ROW, T, E>(
+        &mut self,
+        fetched: DeserializationResult<Option<ROW>>,
+    ) -> Option<Result<T, E>>
+    where
+        ROW: DeserializableRow,
+        <ROW as DeserializableRow>::V: DbValue,
+        T: serde::de::DeserializeOwned,
+        E: From<DeserializationError>,
+    {
+        match fetched {
+            Ok(Some(row)) => {
+                trace!("RowConversion::convert() for row {}", self.row_idx);
+                let row_idx = self.row_idx;
+                self.row_idx += 1;
+                let rd = RowDeserializer::new(row, &self.options);
+                let mut rd = if row_idx == 0 { rd } else { rd.without_report() };
+                Some(
+                    serde::de::Deserialize::deserialize(&mut rd)
+                        .map_err(|e| e.with_row(row_idx).into()),
+                )
+            }
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e.into()))
+            }
+        }
+    }
+}
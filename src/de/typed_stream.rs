@@ -0,0 +1,50 @@
+use futures_core::task::{Context, Poll};
+use futures_core::Stream;
+use log::trace;
+use serde;
+use std::marker::PhantomData;
+use std::pin::Pin;
+
+use crate::de::typed_iter::RowConversion;
+use crate::de::{AsyncDeserializableResultset, DeserializationOptions};
+
+/// Stream over the rows of an asynchronous resultset,
+/// which converts each row into a rust value.
+///
+/// Is returned by
+/// [`AsyncDeserializableResultset::into_typed_stream()`](trait.AsyncDeserializableResultset.html#method.into_typed_stream).
+pub struct TypedStream<RS, T> {
+    rs: RS,
+    conversion: RowConversion,
+    target: PhantomData<fn() -> T>,
+}
+
+impl<RS, T> TypedStream<RS, T> {
+    pub(crate) fn new(rs: RS, options: DeserializationOptions) -> TypedStream<RS, T> {
+        trace!("TypedStream::new()");
+        TypedStream {
+            rs,
+            conversion: RowConversion::new(options),
+            target: PhantomData,
+        }
+    }
+}
+
+impl<RS, T> Stream for TypedStream<RS, T>
+where
+    RS: AsyncDeserializableResultset + Unpin,
+    T: serde::de::DeserializeOwned,
+{
+    type Item = Result<T, RS::E>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.conversion.done {
+            return Poll::Ready(None);
+        }
+        match Pin::new(&mut this.rs).poll_next(cx) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(fetched) => Poll::Ready(this.conversion.convert(fetched)),
+        }
+    }
+}
@@ -9,5 +9,8 @@
 
 #![warn(missing_docs)]
 
+#[cfg(feature = "conformance")]
+pub mod conformance;
+mod conversion_policy;
 pub mod de;
 pub mod ser;
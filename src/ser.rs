@@ -21,7 +21,11 @@
 //! In its implementation of [`DbvFactory`](trait.DbvFactory.html),
 //! the DB driver can decide to make their life even easier
 //! by converting flexibly between
-//! different number types (an example can be found in the tests of this crate)
+//! different number types (an example can be found in the tests of this crate).
+//! Additionally, if a parameter rejects the type of a value, the serializer converts
+//! numbers of different kinds into each other, as far as the
+//! [`ConversionPolicy`](enum.ConversionPolicy.html) allows it,
+//! which the driver can choose with [`to_params_with()`](fn.to_params_with.html).
 //!
 //! The implementation of `add_batch()` converts `input` into
 //! a Vec of the driver's database values that can subsequently be sent to the DB server:
@@ -32,12 +36,24 @@
 //!
 //! It is assumed that the prepared statement has metadata about the required input parameters,
 //! which implement [`DbvFactory`](trait.DbvFactory.html).
+//!
+//! # Enums
+//!
+//! Unit variants are serialized as their name (using `DbvFactory::from_str()`, so
+//! `#[serde(rename)]` is honored), or, if the parameter does not accept a string,
+//! as their index (using `DbvFactory::from_u32()`).
+//! This mirrors the deserialization of enums in [`serde_db::de`](../de/index.html).
+//!
+//! Newtype, tuple and struct variants are rejected with `SerializationError::StructuralMismatch`,
+//! because their content cannot be serialized together with the variant.
 
 mod dbv_factory;
+mod key_serializer;
 mod serialization_error;
 mod serializer;
 
 pub use self::dbv_factory::DbvFactory;
+pub use crate::conversion_policy::ConversionPolicy;
 pub use self::serialization_error::{parse_error, type_error, SerializationError};
 
 use self::serializer::Serializer;
@@ -48,15 +64,83 @@ use serde;
 ///
 /// Database drivers use this method in their implementation (e.g. behind a
 /// `PreparedStatement::add_batch()`).
-pub fn to_params<T: ?Sized, DF: DbvFactory>(
+///
+/// The input must provide a value for each parameter, except for trailing parameters for which
+/// [`DbvFactory::has_default()`](trait.DbvFactory.html#method.has_default) returns true.
+///
+/// Numbers that a parameter rejects are converted with the default conversion policy,
+/// see [`to_params_with()`](fn.to_params_with.html).
+pub fn to_params<T, DF>(
     value: &T,
-    metadata: &mut std::iter::Iterator<Item = DF>,
+    metadata: &mut dyn Iterator<Item = DF>,
 ) -> Result<Vec<DF::DBV>, SerializationError>
 where
-    T: serde::ser::Serialize,
+    T: ?Sized + serde::ser::Serialize,
+    DF: DbvFactory,
 {
     trace!("serde_db::to_params()");
-    let mut serializer = Serializer::new(metadata);
+    to_params_with(value, metadata, ConversionPolicy::default())
+}
+
+/// Like [`to_params()`](fn.to_params.html), with the given conversion policy.
+///
+/// If a parameter rejects the type of a value, the value is converted according to the policy
+/// and handed over again: integers and floats are converted into each other
+/// (with `ConversionPolicy::RangeChecked` only if that is exact,
+/// with `ConversionPolicy::Lossy` also with rounding),
+/// and with `ConversionPolicy::ParseFromString` strings are parsed into booleans and numbers.
+/// With `ConversionPolicy::Strict`, no such fallback is tried.
+pub fn to_params_with<T, DF>(
+    value: &T,
+    metadata: &mut dyn Iterator<Item = DF>,
+    policy: ConversionPolicy,
+) -> Result<Vec<DF::DBV>, SerializationError>
+where
+    T: ?Sized + serde::ser::Serialize,
+    DF: DbvFactory,
+{
+    trace!("serde_db::to_params_with()");
+    let mut serializer = Serializer::new(metadata).with_policy(policy);
+    value.serialize(&mut serializer)?;
+    serializer.into_inner()
+}
+
+/// Provided method that translates the input into a Vec of database values,
+/// binding the fields of a struct, or the entries of a map, to the parameters
+/// with the same name.
+///
+/// The order of the fields is thus irrelevant, while each parameter must provide its name
+/// with [`DbvFactory::name()`](trait.DbvFactory.html#method.name),
+/// and the input must provide exactly one value for each parameter
+/// (except for trailing parameters that have a default).
+///
+/// Numbers that a parameter rejects are converted with the default conversion policy,
+/// see [`to_named_params_with()`](fn.to_named_params_with.html).
+pub fn to_named_params<T, DF>(
+    value: &T,
+    metadata: &mut dyn Iterator<Item = DF>,
+) -> Result<Vec<DF::DBV>, SerializationError>
+where
+    T: ?Sized + serde::ser::Serialize,
+    DF: DbvFactory,
+{
+    trace!("serde_db::to_named_params()");
+    to_named_params_with(value, metadata, ConversionPolicy::default())
+}
+
+/// Like [`to_named_params()`](fn.to_named_params.html), with the given conversion policy,
+/// see [`to_params_with()`](fn.to_params_with.html).
+pub fn to_named_params_with<T, DF>(
+    value: &T,
+    metadata: &mut dyn Iterator<Item = DF>,
+    policy: ConversionPolicy,
+) -> Result<Vec<DF::DBV>, SerializationError>
+where
+    T: ?Sized + serde::ser::Serialize,
+    DF: DbvFactory,
+{
+    trace!("serde_db::to_named_params_with()");
+    let mut serializer = Serializer::new_named(metadata)?.with_policy(policy);
     value.serialize(&mut serializer)?;
-    Ok(serializer.into_inner())
+    serializer.into_inner()
 }
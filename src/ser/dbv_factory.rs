@@ -9,33 +9,92 @@ use super::SerializationError;
 /// A parameter descriptor for a String-valued database type might implement all
 /// methods with adequate conversions, while a parameter descriptor for an integer type might
 /// only support conversions from the rust integer types.
+///
+/// Only the core methods `from_bool()`, `from_i64()`, `from_u64()`, `from_f64()`, `from_str()`,
+/// `from_bytes()`, and `from_none()` must be implemented; the other methods are provided,
+/// and delegate to these, with range checks that fail with `SerializationError::Range`.
+/// They can be overridden, e.g. for database types that require a higher precision.
 pub trait DbvFactory: Sized {
     /// The type of the database objects.
     type DBV;
     /// Serialize a bool.
     fn from_bool(&self, value: bool) -> Result<Self::DBV, SerializationError>;
     /// Serialize an i8.
-    fn from_i8(&self, value: i8) -> Result<Self::DBV, SerializationError>;
+    ///
+    /// The default implementation delegates to `from_i64()`.
+    fn from_i8(&self, value: i8) -> Result<Self::DBV, SerializationError> {
+        self.from_i64(i64::from(value))
+    }
     /// Serialize an i16.
-    fn from_i16(&self, value: i16) -> Result<Self::DBV, SerializationError>;
+    ///
+    /// The default implementation delegates to `from_i64()`.
+    fn from_i16(&self, value: i16) -> Result<Self::DBV, SerializationError> {
+        self.from_i64(i64::from(value))
+    }
     /// Serialize an i32.
-    fn from_i32(&self, value: i32) -> Result<Self::DBV, SerializationError>;
+    ///
+    /// The default implementation delegates to `from_i64()`.
+    fn from_i32(&self, value: i32) -> Result<Self::DBV, SerializationError> {
+        self.from_i64(i64::from(value))
+    }
     /// Serialize an i64.
     fn from_i64(&self, value: i64) -> Result<Self::DBV, SerializationError>;
+    /// Serialize an i128.
+    ///
+    /// The default implementation delegates to `from_i64()` and fails with
+    /// `SerializationError::Range` if the value does not fit into an i64.
+    fn from_i128(&self, value: i128) -> Result<Self::DBV, SerializationError> {
+        if value >= i128::from(i64::MIN) && value <= i128::from(i64::MAX) {
+            self.from_i64(value as i64)
+        } else {
+            Err(SerializationError::Range("i128", self.descriptor()))
+        }
+    }
     /// Serialize an u8.
-    fn from_u8(&self, value: u8) -> Result<Self::DBV, SerializationError>;
+    ///
+    /// The default implementation delegates to `from_u64()`.
+    fn from_u8(&self, value: u8) -> Result<Self::DBV, SerializationError> {
+        self.from_u64(u64::from(value))
+    }
     /// Serialize an u16.
-    fn from_u16(&self, value: u16) -> Result<Self::DBV, SerializationError>;
+    ///
+    /// The default implementation delegates to `from_u64()`.
+    fn from_u16(&self, value: u16) -> Result<Self::DBV, SerializationError> {
+        self.from_u64(u64::from(value))
+    }
     /// Serialize an u32.
-    fn from_u32(&self, value: u32) -> Result<Self::DBV, SerializationError>;
+    ///
+    /// The default implementation delegates to `from_u64()`.
+    fn from_u32(&self, value: u32) -> Result<Self::DBV, SerializationError> {
+        self.from_u64(u64::from(value))
+    }
     /// Serialize an u64.
     fn from_u64(&self, value: u64) -> Result<Self::DBV, SerializationError>;
+    /// Serialize an u128.
+    ///
+    /// The default implementation delegates to `from_u64()` and fails with
+    /// `SerializationError::Range` if the value does not fit into an u64.
+    fn from_u128(&self, value: u128) -> Result<Self::DBV, SerializationError> {
+        if value <= u128::from(u64::MAX) {
+            self.from_u64(value as u64)
+        } else {
+            Err(SerializationError::Range("u128", self.descriptor()))
+        }
+    }
     /// Serialize an f32.
-    fn from_f32(&self, value: f32) -> Result<Self::DBV, SerializationError>;
+    ///
+    /// The default implementation delegates to `from_f64()`.
+    fn from_f32(&self, value: f32) -> Result<Self::DBV, SerializationError> {
+        self.from_f64(f64::from(value))
+    }
     /// Serialize an f64.
     fn from_f64(&self, value: f64) -> Result<Self::DBV, SerializationError>;
     /// Serialize a char.
-    fn from_char(&self, value: char) -> Result<Self::DBV, SerializationError>;
+    ///
+    /// The default implementation delegates to `from_str()`.
+    fn from_char(&self, value: char) -> Result<Self::DBV, SerializationError> {
+        self.from_str(value.encode_utf8(&mut [0; 4]))
+    }
     /// Serialize a str.
     fn from_str(&self, value: &str) -> Result<Self::DBV, SerializationError>;
     /// Serialize bytes.
@@ -44,4 +103,18 @@ pub trait DbvFactory: Sized {
     fn from_none(&self) -> Result<Self::DBV, SerializationError>;
     /// Provide a descriptive String of the type that is required (for error messages).
     fn descriptor(&self) -> String;
+    /// Provide the name of the parameter, if available.
+    ///
+    /// The name is required for binding the values by name, with
+    /// [`to_named_params()`](fn.to_named_params.html).
+    fn name(&self) -> Option<&str> {
+        None
+    }
+    /// Returns true if no value needs to be provided for the parameter,
+    /// e.g. because the database applies a default value.
+    ///
+    /// Only trailing parameters can be omitted.
+    fn has_default(&self) -> bool {
+        false
+    }
 }
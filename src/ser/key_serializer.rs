@@ -0,0 +1,188 @@
+use super::SerializationError;
+
+use log::trace;
+use serde;
+use serde::ser::Impossible;
+
+type SerializationResult<T> = Result<T, SerializationError>;
+
+// Serializes the key of a map entry into a parameter name.
+pub struct KeySerializer;
+
+fn no_name() -> SerializationError {
+    SerializationError::StructuralMismatch("parameter names must be strings")
+}
+
+impl serde::ser::Serializer for KeySerializer {
+    type Ok = String;
+    type Error = SerializationError;
+    type SerializeSeq = Impossible<String, SerializationError>;
+    type SerializeTuple = Impossible<String, SerializationError>;
+    type SerializeTupleStruct = Impossible<String, SerializationError>;
+    type SerializeTupleVariant = Impossible<String, SerializationError>;
+    type SerializeMap = Impossible<String, SerializationError>;
+    type SerializeStruct = Impossible<String, SerializationError>;
+    type SerializeStructVariant = Impossible<String, SerializationError>;
+
+    fn serialize_bool(self, _value: bool) -> SerializationResult<Self::Ok> {
+        Err(no_name())
+    }
+
+    fn serialize_i8(self, _value: i8) -> SerializationResult<Self::Ok> {
+        Err(no_name())
+    }
+
+    fn serialize_i16(self, _value: i16) -> SerializationResult<Self::Ok> {
+        Err(no_name())
+    }
+
+    fn serialize_i32(self, _value: i32) -> SerializationResult<Self::Ok> {
+        Err(no_name())
+    }
+
+    fn serialize_i64(self, _value: i64) -> SerializationResult<Self::Ok> {
+        Err(no_name())
+    }
+
+    fn serialize_i128(self, _value: i128) -> SerializationResult<Self::Ok> {
+        Err(no_name())
+    }
+
+    fn serialize_u8(self, _value: u8) -> SerializationResult<Self::Ok> {
+        Err(no_name())
+    }
+
+    fn serialize_u16(self, _value: u16) -> SerializationResult<Self::Ok> {
+        Err(no_name())
+    }
+
+    fn serialize_u32(self, _value: u32) -> SerializationResult<Self::Ok> {
+        Err(no_name())
+    }
+
+    fn serialize_u64(self, _value: u64) -> SerializationResult<Self::Ok> {
+        Err(no_name())
+    }
+
+    fn serialize_u128(self, _value: u128) -> SerializationResult<Self::Ok> {
+        Err(no_name())
+    }
+
+    fn serialize_f32(self, _value: f32) -> SerializationResult<Self::Ok> {
+        Err(no_name())
+    }
+
+    fn serialize_f64(self, _value: f64) -> SerializationResult<Self::Ok> {
+        Err(no_name())
+    }
+
+    fn serialize_char(self, value: char) -> SerializationResult<Self::Ok> {
+        trace!("KeySerializer::serialize_char()");
+        Ok(value.to_string())
+    }
+
+    fn serialize_str(self, value: &str) -> SerializationResult<Self::Ok> {
+        trace!("KeySerializer::serialize_str() with {}", value);
+        Ok(value.to_string())
+    }
+
+    fn serialize_bytes(self, _value: &[u8]) -> SerializationResult<Self::Ok> {
+        Err(no_name())
+    }
+
+    fn serialize_unit(self) -> SerializationResult<Self::Ok> {
+        Err(no_name())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> SerializationResult<Self::Ok> {
+        Err(no_name())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> SerializationResult<Self::Ok> {
+        trace!("KeySerializer::serialize_unit_variant()");
+        Ok(variant.to_string())
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + serde::ser::Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> SerializationResult<Self::Ok> {
+        trace!("KeySerializer::serialize_newtype_struct()");
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + serde::ser::Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> SerializationResult<Self::Ok> {
+        Err(no_name())
+    }
+
+    fn serialize_none(self) -> SerializationResult<Self::Ok> {
+        Err(no_name())
+    }
+
+    fn serialize_some<T: ?Sized + serde::ser::Serialize>(
+        self,
+        _value: &T,
+    ) -> SerializationResult<Self::Ok> {
+        Err(no_name())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> SerializationResult<Self::SerializeSeq> {
+        Err(no_name())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> SerializationResult<Self::SerializeTuple> {
+        Err(no_name())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> SerializationResult<Self::SerializeTupleStruct> {
+        Err(no_name())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> SerializationResult<Self::SerializeTupleVariant> {
+        Err(no_name())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> SerializationResult<Self::SerializeMap> {
+        Err(no_name())
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> SerializationResult<Self::SerializeStruct> {
+        Err(no_name())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> SerializationResult<Self::SerializeStructVariant> {
+        Err(no_name())
+    }
+}
@@ -27,6 +27,28 @@ pub enum SerializationError {
     },
     /// The input value is too big or too small for the required database type.
     Range(&'static str, String),
+    /// The value provides fewer values than parameters are required.
+    TooFewValues {
+        /// Number of required parameters
+        expected: usize,
+        /// Number of provided values
+        supplied: usize,
+    },
+    /// The value provides a field for which no parameter with this name exists.
+    UnknownParameter(String),
+    /// The value provides more than one value for the parameter with this name.
+    DuplicateParameter(String),
+    /// The value provides no value for the parameter with this name.
+    MissingParameter(String),
+    /// Serializing a specific parameter failed.
+    Parameter {
+        /// Index of the parameter.
+        index: usize,
+        /// Name of the struct field that was serialized, if known.
+        name: Option<String>,
+        /// The error that occured while serializing the parameter.
+        cause: Box<SerializationError>,
+    },
 }
 
 /// Factory for Parse Error.
@@ -61,6 +83,11 @@ impl Error for SerializationError {
             },
             SerializationError::Type { .. } => "type mismatch",
             SerializationError::Range(_, _) => "range exceeded",
+            SerializationError::TooFewValues { .. } => "too few values",
+            SerializationError::UnknownParameter(_) => "unknown parameter",
+            SerializationError::DuplicateParameter(_) => "duplicate parameter",
+            SerializationError::MissingParameter(_) => "missing parameter",
+            SerializationError::Parameter { .. } => "parameter cannot be serialized",
         }
     }
     fn cause(&self) -> Option<&Error> {
@@ -77,6 +104,11 @@ impl Error for SerializationError {
             },
             SerializationError::Type { .. } => None,
             SerializationError::Range(_, _) => None,
+            SerializationError::TooFewValues { .. }
+            | SerializationError::UnknownParameter(_)
+            | SerializationError::DuplicateParameter(_)
+            | SerializationError::MissingParameter(_) => None,
+            SerializationError::Parameter { ref cause, .. } => Some(&**cause),
         }
     }
 }
@@ -129,6 +161,27 @@ impl fmt::Debug for SerializationError {
                 "given value of type \"{}\" does not fit into supported range of SQL type {}",
                 s1, s2
             ),
+            SerializationError::TooFewValues {
+                ref expected,
+                ref supplied,
+            } => write!(
+                fmt,
+                "{} values are required, but only {} values are given",
+                expected, supplied
+            ),
+            SerializationError::UnknownParameter(ref s) => write!(fmt, "unknown parameter: {}", s),
+            SerializationError::DuplicateParameter(ref s) => {
+                write!(fmt, "duplicate parameter: {}", s)
+            }
+            SerializationError::MissingParameter(ref s) => write!(fmt, "missing parameter: {}", s),
+            SerializationError::Parameter {
+                ref index,
+                ref name,
+                ref cause,
+            } => match name {
+                Some(name) => write!(fmt, "parameter {} (#{}): {:?}", name, index, cause),
+                None => write!(fmt, "parameter #{}: {:?}", index, cause),
+            },
         }
     }
 }
@@ -1,43 +1,249 @@
 use super::dbv_factory::DbvFactory;
 use super::type_error;
-use super::SerializationError;
+use super::{ConversionPolicy, SerializationError};
 
+use super::key_serializer::KeySerializer;
 use log::{log_enabled, trace};
 use serde;
+use std::borrow::Cow;
 
 type SerializationResult<T> = Result<T, SerializationError>;
 
 /// A structure for serializing Rust values into a parameter row for a prepared statement.
 pub struct Serializer<'m, DF: 'm + DbvFactory> {
     output: Vec<DF::DBV>,
-    metadata: &'m mut std::iter::Iterator<Item = DF>,
+    metadata: &'m mut dyn Iterator<Item = DF>,
+    // the name of the struct field or map key that is currently serialized
+    field_name: Option<Cow<'static, str>>,
+    // is only used for binding the values by name
+    named: Option<NamedParams<DF>>,
+    // decides which fallbacks are tried if a parameter rejects the type of a value
+    policy: ConversionPolicy,
+}
+
+// The parameters, with their names, for binding values by name.
+struct NamedParams<DF: DbvFactory> {
+    names: Vec<String>,
+    factories: Vec<Option<DF>>,
+    values: Vec<Option<DF::DBV>>,
+    current: usize,
 }
 
 impl<'m, DF: DbvFactory> Serializer<'m, DF> {
-    pub fn new(metadata: &'m mut std::iter::Iterator<Item = DF>) -> Self {
+    pub fn new(metadata: &'m mut dyn Iterator<Item = DF>) -> Self {
         Serializer {
             output: Vec::<DF::DBV>::new(),
             metadata,
+            field_name: None,
+            named: None,
+            policy: ConversionPolicy::default(),
         }
     }
+
+    // Consumes the metadata completely, because the values are bound by name.
+    pub fn new_named(metadata: &'m mut dyn Iterator<Item = DF>) -> SerializationResult<Self> {
+        let mut named = NamedParams {
+            names: Vec::<String>::new(),
+            factories: Vec::<Option<DF>>::new(),
+            values: Vec::<Option<DF::DBV>>::new(),
+            current: 0,
+        };
+        for df in &mut *metadata {
+            let name = match df.name() {
+                Some(name) => name.to_string(),
+                None => {
+                    return Err(SerializationError::StructuralMismatch(
+                        "parameter without name, binding by name not possible",
+                    ));
+                }
+            };
+            named.names.push(name);
+            named.factories.push(Some(df));
+            named.values.push(None);
+        }
+        Ok(Serializer {
+            output: Vec::<DF::DBV>::new(),
+            metadata,
+            field_name: None,
+            named: Some(named),
+            policy: ConversionPolicy::default(),
+        })
+    }
+
+    pub fn with_policy(mut self, policy: ConversionPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
     fn get_current_field(&mut self) -> SerializationResult<DF> {
-        match self.metadata.next() {
-            Some(df) => Ok(df),
-            None => Err(SerializationError::StructuralMismatch(
-                "too many values specified",
-            )),
+        match self.named {
+            Some(ref mut named) => {
+                let name = match self.field_name {
+                    Some(ref name) => name,
+                    None => {
+                        return Err(SerializationError::StructuralMismatch(
+                            "binding by name requires a struct or a map",
+                        ));
+                    }
+                };
+                match named.names.iter().position(|n| n == name) {
+                    Some(idx) => {
+                        named.current = idx;
+                        named.factories[idx]
+                            .take()
+                            .ok_or_else(|| SerializationError::DuplicateParameter(name.to_string()))
+                    }
+                    None => Err(SerializationError::UnknownParameter(name.to_string())),
+                }
+            }
+            None => match self.metadata.next() {
+                Some(df) => Ok(df),
+                None => Err(SerializationError::StructuralMismatch(
+                    "too many values specified",
+                )),
+            },
+        }
+    }
+
+    // Adds the position of the current parameter to the error.
+    fn located(&self, e: SerializationError) -> SerializationError {
+        match e {
+            SerializationError::Parameter { .. } => e,
+            e => SerializationError::Parameter {
+                index: match self.named {
+                    Some(ref named) => named.current,
+                    None => self.output.len(),
+                },
+                name: self.field_name.as_ref().map(|name| name.to_string()),
+                cause: Box::new(e),
+            },
+        }
+    }
+
+    // If the parameter rejects an integer, it is converted into a float,
+    // if the policy allows it.
+    fn integer_fallback(
+        &self,
+        df: &DF,
+        result: SerializationResult<DF::DBV>,
+        value: f64,
+        exact: bool,
+    ) -> SerializationResult<DF::DBV> {
+        match result {
+            Err(e @ SerializationError::Type { .. })
+                if self.policy >= ConversionPolicy::Lossy
+                    || (exact && self.policy >= ConversionPolicy::RangeChecked) =>
+            {
+                df.from_f64(value).map_err(|_| e)
+            }
+            result => result,
+        }
+    }
+
+    // If the parameter rejects a float, it is converted into an integer,
+    // if the policy allows it; fractions are only rounded with the lossy policies.
+    fn float_fallback(
+        &self,
+        df: &DF,
+        result: SerializationResult<DF::DBV>,
+        value: f64,
+    ) -> SerializationResult<DF::DBV> {
+        match result {
+            Err(e @ SerializationError::Type { .. })
+                if self.policy >= ConversionPolicy::RangeChecked && value.is_finite() =>
+            {
+                let rounded = value.round();
+                if (rounded == value || self.policy >= ConversionPolicy::Lossy)
+                    && rounded.abs() < 9.2e18
+                {
+                    df.from_i64(rounded as i64).map_err(|_| e)
+                } else {
+                    Err(e)
+                }
+            }
+            result => result,
+        }
+    }
+
+    // If the parameter rejects a string, it is parsed into a bool or a number,
+    // if the policy allows it; the number is then handled like a serialized number.
+    fn str_fallback(
+        &self,
+        df: &DF,
+        result: SerializationResult<DF::DBV>,
+        value: &str,
+    ) -> SerializationResult<DF::DBV> {
+        match result {
+            Err(e @ SerializationError::Type { .. })
+                if self.policy >= ConversionPolicy::ParseFromString =>
+            {
+                let value = value.trim();
+                if let Ok(b) = value.parse() {
+                    df.from_bool(b).map_err(|_| e)
+                } else if let Ok(i) = value.parse::<i64>() {
+                    let exact = exact_in_f64(i128::from(i));
+                    self.integer_fallback(df, df.from_i64(i), i as f64, exact).map_err(|_| e)
+                } else if let Ok(f) = value.parse() {
+                    self.float_fallback(df, df.from_f64(f), f).map_err(|_| e)
+                } else {
+                    Err(e)
+                }
+            }
+            result => result,
         }
     }
 
     fn push(&mut self, value: DF::DBV) {
-        self.output.push(value);
+        match self.named {
+            Some(ref mut named) => named.values[named.current] = Some(value),
+            None => self.output.push(value),
+        }
     }
 
-    pub fn into_inner(self) -> Vec<DF::DBV> {
-        self.output
+    // Fails if parameters remain without value, unless they are trailing and have a default.
+    pub fn into_inner(self) -> SerializationResult<Vec<DF::DBV>> {
+        match self.named {
+            Some(named) => {
+                let mut len = named.values.len();
+                while len > 0 && named.values[len - 1].is_none() {
+                    match named.factories[len - 1] {
+                        Some(ref df) if df.has_default() => len -= 1,
+                        _ => break,
+                    }
+                }
+                named
+                    .values
+                    .into_iter()
+                    .zip(named.names)
+                    .take(len)
+                    .map(|(value, name)| {
+                        value.ok_or_else(|| SerializationError::MissingParameter(name))
+                    })
+                    .collect()
+            }
+            None => {
+                let supplied = self.output.len();
+                let mut expected = supplied;
+                let mut all_have_default = true;
+                for df in self.metadata {
+                    expected += 1;
+                    all_have_default &= df.has_default();
+                }
+                if all_have_default {
+                    Ok(self.output)
+                } else {
+                    Err(SerializationError::TooFewValues { expected, supplied })
+                }
+            }
+        }
     }
 }
 
+// Integers with more significant bits than the mantissa of an f64 are not converted exactly.
+fn exact_in_f64(value: i128) -> bool {
+    (-(1 << 53)..=(1 << 53)).contains(&value)
+}
+
 impl<'a, 'm: 'a, DF: DbvFactory> serde::ser::Serializer for &'a mut Serializer<'m, DF> {
     type Ok = ();
     type Error = SerializationError;
@@ -51,84 +257,142 @@ impl<'a, 'm: 'a, DF: DbvFactory> serde::ser::Serializer for &'a mut Serializer<'
 
     fn serialize_bool(self, value: bool) -> SerializationResult<Self::Ok> {
         trace!("Serializer::serialize_bool()");
-        let val = self.get_current_field()?.from_bool(value)?;
+        let val = self
+            .get_current_field()?
+            .from_bool(value)
+            .map_err(|e| self.located(e))?;
         self.push(val);
         Ok(())
     }
 
     fn serialize_i8(self, value: i8) -> SerializationResult<Self::Ok> {
         trace!("Serializer::serialize_i8()");
-        let val = self.get_current_field()?.from_i8(value)?;
+        let df = self.get_current_field()?;
+        let val = self
+            .integer_fallback(&df, df.from_i8(value), f64::from(value), true)
+            .map_err(|e| self.located(e))?;
         self.push(val);
         Ok(())
     }
 
     fn serialize_i16(self, value: i16) -> SerializationResult<Self::Ok> {
         trace!("Serializer::serialize_i16()");
-        let val = self.get_current_field()?.from_i16(value)?;
+        let df = self.get_current_field()?;
+        let val = self
+            .integer_fallback(&df, df.from_i16(value), f64::from(value), true)
+            .map_err(|e| self.located(e))?;
         self.push(val);
         Ok(())
     }
 
     fn serialize_i32(self, value: i32) -> SerializationResult<Self::Ok> {
         trace!("Serializer::serialize_i32()");
-        let val = self.get_current_field()?.from_i32(value)?;
+        let df = self.get_current_field()?;
+        let val = self
+            .integer_fallback(&df, df.from_i32(value), f64::from(value), true)
+            .map_err(|e| self.located(e))?;
         self.push(val);
         Ok(())
     }
 
     fn serialize_i64(self, value: i64) -> SerializationResult<Self::Ok> {
         trace!("Serializer::serialize_i64()");
-        let val = self.get_current_field()?.from_i64(value)?;
+        let df = self.get_current_field()?;
+        let exact = exact_in_f64(i128::from(value));
+        let val = self
+            .integer_fallback(&df, df.from_i64(value), value as f64, exact)
+            .map_err(|e| self.located(e))?;
+        self.push(val);
+        Ok(())
+    }
+
+    fn serialize_i128(self, value: i128) -> SerializationResult<Self::Ok> {
+        trace!("Serializer::serialize_i128()");
+        let df = self.get_current_field()?;
+        let val = self
+            .integer_fallback(&df, df.from_i128(value), value as f64, exact_in_f64(value))
+            .map_err(|e| self.located(e))?;
         self.push(val);
         Ok(())
     }
 
     fn serialize_u8(self, value: u8) -> SerializationResult<Self::Ok> {
         trace!("Serializer::serialize_u8()");
-        let val = self.get_current_field()?.from_u8(value)?;
+        let df = self.get_current_field()?;
+        let val = self
+            .integer_fallback(&df, df.from_u8(value), f64::from(value), true)
+            .map_err(|e| self.located(e))?;
         self.push(val);
         Ok(())
     }
 
     fn serialize_u16(self, value: u16) -> SerializationResult<Self::Ok> {
         trace!("Serializer::serialize_u16()");
-        let val = self.get_current_field()?.from_u16(value)?;
+        let df = self.get_current_field()?;
+        let val = self
+            .integer_fallback(&df, df.from_u16(value), f64::from(value), true)
+            .map_err(|e| self.located(e))?;
         self.push(val);
         Ok(())
     }
 
     fn serialize_u32(self, value: u32) -> SerializationResult<Self::Ok> {
         trace!("Serializer::serialize_u32()");
-        let val = self.get_current_field()?.from_u32(value)?;
+        let df = self.get_current_field()?;
+        let val = self
+            .integer_fallback(&df, df.from_u32(value), f64::from(value), true)
+            .map_err(|e| self.located(e))?;
         self.push(val);
         Ok(())
     }
 
     fn serialize_u64(self, value: u64) -> SerializationResult<Self::Ok> {
         trace!("Serializer::serialize_u64()");
-        let val = self.get_current_field()?.from_u64(value)?;
+        let df = self.get_current_field()?;
+        let exact = exact_in_f64(i128::from(value));
+        let val = self
+            .integer_fallback(&df, df.from_u64(value), value as f64, exact)
+            .map_err(|e| self.located(e))?;
+        self.push(val);
+        Ok(())
+    }
+
+    fn serialize_u128(self, value: u128) -> SerializationResult<Self::Ok> {
+        trace!("Serializer::serialize_u128()");
+        let df = self.get_current_field()?;
+        let val = self
+            .integer_fallback(&df, df.from_u128(value), value as f64, value <= 1 << 53)
+            .map_err(|e| self.located(e))?;
         self.push(val);
         Ok(())
     }
 
     fn serialize_f32(self, value: f32) -> SerializationResult<Self::Ok> {
         trace!("Serializer::serialize_f32()");
-        let val = self.get_current_field()?.from_f32(value)?;
+        let df = self.get_current_field()?;
+        let val = self
+            .float_fallback(&df, df.from_f32(value), f64::from(value))
+            .map_err(|e| self.located(e))?;
         self.push(val);
         Ok(())
     }
 
     fn serialize_f64(self, value: f64) -> SerializationResult<Self::Ok> {
         trace!("Serializer::serialize_f64()");
-        let val = self.get_current_field()?.from_f64(value)?;
+        let df = self.get_current_field()?;
+        let val = self
+            .float_fallback(&df, df.from_f64(value), value)
+            .map_err(|e| self.located(e))?;
         self.push(val);
         Ok(())
     }
 
     fn serialize_char(self, value: char) -> SerializationResult<Self::Ok> {
         trace!("Serializer::serialize_char()");
-        let val = self.get_current_field()?.from_char(value)?;
+        let val = self
+            .get_current_field()?
+            .from_char(value)
+            .map_err(|e| self.located(e))?;
         self.push(val);
         Ok(())
     }
@@ -146,44 +410,60 @@ impl<'a, 'm: 'a, DF: DbvFactory> serde::ser::Serializer for &'a mut Serializer<'
                 );
             }
         }
-        let val = self.get_current_field()?.from_str(value)?;
+        let df = self.get_current_field()?;
+        let val = self
+            .str_fallback(&df, df.from_str(value), value)
+            .map_err(|e| self.located(e))?;
         self.push(val);
         Ok(())
     }
 
     fn serialize_bytes(self, value: &[u8]) -> SerializationResult<Self::Ok> {
         trace!("Serializer::serialize_bytes()");
-        let val = self.get_current_field()?.from_bytes(value)?;
+        let val = self
+            .get_current_field()?
+            .from_bytes(value)
+            .map_err(|e| self.located(e))?;
         self.push(val);
         Ok(())
     }
 
     fn serialize_unit(self) -> SerializationResult<Self::Ok> {
         trace!("Serializer::serialize_unit()");
-        let val = self.get_current_field()?.from_none()?;
+        let val = self
+            .get_current_field()?
+            .from_none()
+            .map_err(|e| self.located(e))?;
         self.push(val);
         Ok(())
     }
 
     fn serialize_unit_struct(self, _name: &'static str) -> SerializationResult<Self::Ok> {
         trace!("Serializer::serialize_unit_struct()");
-        Err(type_error(
-            "unit_struct",
-            self.get_current_field()?.descriptor(),
-        ))
+        let e = type_error("unit_struct", self.get_current_field()?.descriptor());
+        Err(self.located(e))
     }
 
+    // Unit variants are serialized as their name, or, if the parameter does not accept
+    // a string, as their index.
     fn serialize_unit_variant(
         self,
         _name: &'static str,
-        _variant_index: u32,
-        _variant: &'static str,
+        variant_index: u32,
+        variant: &'static str,
     ) -> SerializationResult<Self::Ok> {
         trace!("Serializer::serialize_unit_variant()");
-        Err(type_error(
-            "unit_variant",
-            self.get_current_field()?.descriptor(),
-        ))
+        let df = self.get_current_field()?;
+        let val = match df.from_str(variant) {
+            Ok(val) => val,
+            Err(e @ SerializationError::Type { .. })
+            | Err(e @ SerializationError::Parse { .. }) => {
+                df.from_u32(variant_index).map_err(|_| self.located(e))?
+            }
+            Err(e) => return Err(self.located(e)),
+        };
+        self.push(val);
+        Ok(())
     }
 
     fn serialize_newtype_struct<T: ?Sized + serde::ser::Serialize>(
@@ -200,15 +480,20 @@ impl<'a, 'm: 'a, DF: DbvFactory> serde::ser::Serializer for &'a mut Serializer<'
         _name: &'static str,
         _variant_index: u32,
         _variant: &'static str,
-        value: &T,
+        _value: &T,
     ) -> SerializationResult<Self::Ok> {
         trace!("Serializer::serialize_newtype_variant()");
-        value.serialize(self)
+        Err(self.located(SerializationError::StructuralMismatch(
+            "enum variants with data cannot be serialized into parameters",
+        )))
     }
 
     fn serialize_none(self) -> SerializationResult<Self::Ok> {
         trace!("Serializer::serialize_none()");
-        let val = self.get_current_field()?.from_none()?;
+        let val = self
+            .get_current_field()?
+            .from_none()
+            .map_err(|e| self.located(e))?;
         self.push(val);
         Ok(())
     }
@@ -248,7 +533,9 @@ impl<'a, 'm: 'a, DF: DbvFactory> serde::ser::Serializer for &'a mut Serializer<'
         _len: usize,
     ) -> SerializationResult<Self::SerializeTupleVariant> {
         trace!("Serializer::serialize_tuple_variant()");
-        Ok(Compound { ser: self })
+        Err(self.located(SerializationError::StructuralMismatch(
+            "enum variants with data cannot be serialized into parameters",
+        )))
     }
 
     fn serialize_map(self, _len: Option<usize>) -> SerializationResult<Self::SerializeMap> {
@@ -272,9 +559,10 @@ impl<'a, 'm: 'a, DF: DbvFactory> serde::ser::Serializer for &'a mut Serializer<'
         _variant: &'static str,
         _len: usize,
     ) -> SerializationResult<Self::SerializeStructVariant> {
-        Err(SerializationError::StructuralMismatch(
-            "serialize_struct_variant() not implemented",
-        ))
+        trace!("Serializer::serialize_struct_variant()");
+        Err(self.located(SerializationError::StructuralMismatch(
+            "enum variants with data cannot be serialized into parameters",
+        )))
     }
 }
 
@@ -370,11 +658,15 @@ impl<'a, 'm, DF: 'm + DbvFactory> serde::ser::SerializeMap for Compound<'a, 'm,
     type Ok = ();
     type Error = SerializationError;
 
-    fn serialize_key<T: ?Sized>(&mut self, _key: &T) -> SerializationResult<()>
+    // The keys are only evaluated when the values are bound by name.
+    fn serialize_key<T: ?Sized>(&mut self, key: &T) -> SerializationResult<()>
     where
         T: serde::ser::Serialize,
     {
         trace!("Compound: SerializeMap::serialize_key()");
+        if self.ser.named.is_some() {
+            self.ser.field_name = Some(Cow::Owned(key.serialize(KeySerializer)?));
+        }
         Ok(())
     }
 
@@ -384,7 +676,9 @@ impl<'a, 'm, DF: 'm + DbvFactory> serde::ser::SerializeMap for Compound<'a, 'm,
     {
         trace!("Compound: SerializeMap::serialize_value()");
         let t: &mut Serializer<DF> = self.ser;
-        value.serialize(t)
+        let result = value.serialize(&mut *t);
+        t.field_name = None;
+        result
     }
 
     fn end(self) -> SerializationResult<Self::Ok> {
@@ -406,8 +700,10 @@ impl<'a, 'm, DF: 'm + DbvFactory> serde::ser::SerializeStruct for Compound<'a, '
         T: serde::ser::Serialize,
     {
         trace!("Compound: SerializeStruct::serialize_field()");
-        r#try!(serde::ser::SerializeMap::serialize_key(self, key));
-        serde::ser::SerializeMap::serialize_value(self, value)
+        let outer_field_name = self.ser.field_name.replace(Cow::Borrowed(key));
+        let result = serde::ser::SerializeMap::serialize_value(self, value);
+        self.ser.field_name = outer_field_name;
+        result
     }
 
     fn end(self) -> SerializationResult<Self::Ok> {
@@ -0,0 +1,48 @@
+use serde_db::de::{AsyncDeserializableResultset, DeserializationResult};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use crate::mock_db;
+
+// In-memory stand-in for a resultset whose rows are fetched asynchronously:
+// every row is only available after a Poll::Pending.
+#[derive(Debug)]
+pub struct AsyncResultset {
+    rs: mock_db::Resultset,
+    available: bool,
+}
+impl AsyncResultset {
+    pub fn new(rs: mock_db::Resultset) -> AsyncResultset {
+        AsyncResultset {
+            rs,
+            available: false,
+        }
+    }
+}
+
+impl AsyncDeserializableResultset for AsyncResultset {
+    type E = mock_db::Error;
+    type ROW = mock_db::Row;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context,
+    ) -> Poll<DeserializationResult<Option<mock_db::Row>>> {
+        if self.available {
+            self.available = false;
+            Poll::Ready(Ok(self.rs.next()))
+        } else {
+            self.available = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+
+    fn number_of_fields(&self) -> usize {
+        self.rs.number_of_fields()
+    }
+
+    fn fieldname(&self, field_idx: usize) -> Option<&String> {
+        self.rs.fieldname(field_idx)
+    }
+}
@@ -1,6 +1,8 @@
 // not all tests need all classes
 #![allow(dead_code)]
 
+#[cfg(feature = "async")]
+mod async_resultset;
 mod error;
 mod fieldnames;
 mod mvalue;
@@ -9,6 +11,9 @@ mod resultset;
 mod row;
 mod serde_db_impl;
 
+#[cfg(feature = "async")]
+#[allow(unused_imports)]
+pub use self::async_resultset::*;
 pub use self::error::*;
 pub use self::fieldnames::*;
 pub use self::parameter_type::*;
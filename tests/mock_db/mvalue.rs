@@ -36,6 +36,9 @@ pub enum MValue {
     NullableString(Option<String>),
     Timestamp(Timestamp),
     NullableTimestamp(Option<Timestamp>),
+    // simulate values that borrow from a buffer of the driver
+    BorrowedString(&'static str),
+    BorrowedBytes(&'static [u8]),
 }
 
 impl MValue {
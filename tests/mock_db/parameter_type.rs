@@ -7,3 +7,6 @@ pub enum ParameterType {
     Timestamp,
     NullableTimestamp,
 }
+
+// A parameter with a name, which allows binding values by name
+pub struct NamedParameter(pub &'static str, pub ParameterType);
@@ -1,5 +1,5 @@
 use serde;
-use serde_db::de::DeserializableResultset;
+use serde_db::de::{DeserializableResultset, DeserializationOptions, TypedIter};
 use std::rc::Rc;
 
 use crate::mock_db;
@@ -63,6 +63,72 @@ impl Resultset {
         trace!("Resultset::try_into()");
         Ok(DeserializableResultset::into_typed(self)?)
     }
+
+    pub fn try_into_with<'de, T>(self, options: &DeserializationOptions) -> mock_db::Result<T>
+    where
+        T: serde::de::Deserialize<'de>,
+    {
+        trace!("Resultset::try_into_with()");
+        Ok(DeserializableResultset::into_typed_with(self, options)?)
+    }
+
+    pub fn into_typed_iter<T>(self) -> TypedIter<Resultset, T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        trace!("Resultset::into_typed_iter()");
+        DeserializableResultset::into_typed_iter(self)
+    }
+
+    pub fn into_typed_iter_with<T>(
+        self,
+        options: &DeserializationOptions,
+    ) -> TypedIter<Resultset, T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        trace!("Resultset::into_typed_iter_with()");
+        DeserializableResultset::into_typed_iter_with(self, options)
+    }
+
+    pub fn try_into_grouped<'de, T>(self, key_columns: &[&str]) -> mock_db::Result<Vec<T>>
+    where
+        T: serde::de::Deserialize<'de>,
+    {
+        trace!("Resultset::try_into_grouped()");
+        Ok(DeserializableResultset::into_grouped(self, key_columns)?)
+    }
+
+    pub fn try_into_grouped_with<'de, T>(
+        self,
+        key_columns: &[&str],
+        options: &DeserializationOptions,
+    ) -> mock_db::Result<Vec<T>>
+    where
+        T: serde::de::Deserialize<'de>,
+    {
+        trace!("Resultset::try_into_grouped_with()");
+        Ok(DeserializableResultset::into_grouped_with(self, key_columns, options)?)
+    }
+
+    pub fn try_into_columns<'de, T>(self) -> mock_db::Result<T>
+    where
+        T: serde::de::Deserialize<'de>,
+    {
+        trace!("Resultset::try_into_columns()");
+        Ok(DeserializableResultset::into_columns(self)?)
+    }
+
+    pub fn try_into_columns_with<'de, T>(
+        self,
+        options: &DeserializationOptions,
+    ) -> mock_db::Result<T>
+    where
+        T: serde::de::Deserialize<'de>,
+    {
+        trace!("Resultset::try_into_columns_with()");
+        Ok(DeserializableResultset::into_columns_with(self, options)?)
+    }
 }
 
 impl Iterator for Resultset {
@@ -1,5 +1,5 @@
 use serde;
-use serde_db::de::{DbValue, DeserializableRow, DeserializationError};
+use serde_db::de::{DbValue, DeserializableRow, DeserializationError, DeserializationOptions};
 use std::rc::Rc;
 
 use crate::mock_db;
@@ -56,6 +56,14 @@ impl Row {
         trace!("Row::try_into()");
         Ok(DeserializableRow::into_typed(self)?)
     }
+
+    pub fn try_into_with<'de, T>(self, options: &DeserializationOptions) -> mock_db::Result<T>
+    where
+        T: serde::de::Deserialize<'de>,
+    {
+        trace!("Row::try_into_with()");
+        Ok(DeserializableRow::into_typed_with(self, options)?)
+    }
 }
 
 impl DeserializableRow for mock_db::Row {
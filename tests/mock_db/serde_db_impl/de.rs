@@ -13,10 +13,40 @@ impl DbValue for MValue {
     fn is_null(&self) -> bool {
         trace!("is_null() for {:?}", self);
         match *self {
-            MValue::NullableDouble(None) | MValue::NullableShort(None) => true,
+            MValue::NullableDouble(None)
+            | MValue::NullableShort(None)
+            | MValue::NullableString(None)
+            | MValue::NullableTimestamp(None) => true,
             _ => false,
         }
     }
+
+    fn is_integer(&self) -> bool {
+        match *self {
+            MValue::Short(_) | MValue::NullableShort(Some(_)) => true,
+            _ => false,
+        }
+    }
+
+    fn as_str<'de>(&self) -> Option<&'de str>
+    where
+        Self: 'de,
+    {
+        match *self {
+            MValue::BorrowedString(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn as_bytes<'de>(&self) -> Option<&'de [u8]>
+    where
+        Self: 'de,
+    {
+        match *self {
+            MValue::BorrowedBytes(b) => Some(b),
+            _ => None,
+        }
+    }
 }
 
 impl DbValueInto<bool> for MValue {
@@ -36,7 +66,22 @@ impl DbValueInto<u16> for MValue {
 }
 impl DbValueInto<u32> for MValue {
     fn try_into(self) -> Result<u32, ConversionError> {
-        Err(not_implemented("DbValueInto<u32>"))
+        match self {
+            MValue::Short(i) | MValue::NullableShort(Some(i)) => {
+                if i >= 0 {
+                    Ok(i as u32)
+                } else {
+                    Err(ConversionError::NumberRange(format!(
+                        "{} cannot be converted into u32",
+                        i
+                    )))
+                }
+            }
+            mv => Err(ConversionError::ValueType(format!(
+                "DbValueInto<u32> not implemented for {:?}",
+                mv
+            ))),
+        }
     }
 }
 impl DbValueInto<u64> for MValue {
@@ -73,7 +118,13 @@ impl DbValueInto<i32> for MValue {
 }
 impl DbValueInto<i64> for MValue {
     fn try_into(self) -> Result<i64, ConversionError> {
-        Err(not_implemented("DbValueInto<i64>"))
+        match self {
+            MValue::Short(i) | MValue::NullableShort(Some(i)) => Ok(i64::from(i)),
+            mv => Err(ConversionError::ValueType(format!(
+                "DbValueInto<i64> not implemented for {:?}",
+                mv
+            ))),
+        }
     }
 }
 impl DbValueInto<f32> for MValue {
@@ -96,7 +147,9 @@ impl DbValueInto<String> for MValue {
     fn try_into(self) -> Result<String, ConversionError> {
         trace!("try_into -> String");
         match self {
-            MValue::String(s) => Ok(s),
+            MValue::String(s) | MValue::NullableString(Some(s)) => Ok(s),
+            MValue::BorrowedString(s) => Ok(s.to_string()),
+            MValue::Short(i) | MValue::NullableShort(Some(i)) => Ok(i.to_string()),
             MValue::Timestamp(ts) => Ok(ts.to_string()),
             MValue::Double(f) | MValue::NullableDouble(Some(f)) => Ok(f.to_string()),
             mv => Err(ConversionError::ValueType(format!(
@@ -108,7 +161,13 @@ impl DbValueInto<String> for MValue {
 }
 impl DbValueInto<Vec<u8>> for MValue {
     fn try_into(self) -> Result<Vec<u8>, ConversionError> {
-        Err(not_implemented("DbValueInto<Vec<u8>>"))
+        match self {
+            MValue::BorrowedBytes(b) => Ok(b.to_vec()),
+            mv => Err(ConversionError::ValueType(format!(
+                "DbValueInto<Vec<u8>> not implemented for {:?}",
+                mv
+            ))),
+        }
     }
 }
 
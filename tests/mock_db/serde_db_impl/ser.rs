@@ -4,7 +4,7 @@ use std::str::FromStr;
 
 use crate::mock_db;
 use crate::mock_db::MValue;
-use crate::mock_db::ParameterType;
+use crate::mock_db::{NamedParameter, ParameterType};
 use serde_db::ser::{type_error, DbvFactory, SerializationError};
 use std::{i16, i32, i8, u16, u32, u8};
 
@@ -73,8 +73,24 @@ impl DbvFactory for &ParameterType {
     fn from_u16(&self, _value: u16) -> Result<Self::DBV, SerializationError> {
         Err(type_error("u16", self.descriptor()))
     }
-    fn from_u32(&self, _value: u32) -> Result<Self::DBV, SerializationError> {
-        Err(type_error("u32", self.descriptor()))
+    fn from_u32(&self, value: u32) -> Result<Self::DBV, SerializationError> {
+        match *self {
+            ParameterType::Short => {
+                if value <= i16::MAX as u32 {
+                    Ok(MValue::Short(value as i16))
+                } else {
+                    Err(SerializationError::Range("u32", self.descriptor()))
+                }
+            }
+            ParameterType::NullableShort => {
+                if value <= i16::MAX as u32 {
+                    Ok(MValue::NullableShort(Some(value as i16)))
+                } else {
+                    Err(SerializationError::Range("u32", self.descriptor()))
+                }
+            }
+            _ => Err(type_error("u32", self.descriptor())),
+        }
     }
     fn from_u64(&self, _value: u64) -> Result<Self::DBV, SerializationError> {
         Err(type_error("u64", self.descriptor()))
@@ -120,6 +136,74 @@ impl DbvFactory for &ParameterType {
             ParameterType::NullableTimestamp => "NullableTimestamp",
         })
     }
+    // nullable parameters default to NULL
+    fn has_default(&self) -> bool {
+        match *self {
+            ParameterType::NullableShort
+            | ParameterType::NullableString
+            | ParameterType::NullableTimestamp => true,
+            ParameterType::Short | ParameterType::String | ParameterType::Timestamp => false,
+        }
+    }
+}
+
+impl DbvFactory for &NamedParameter {
+    type DBV = MValue;
+
+    fn from_bool(&self, value: bool) -> Result<Self::DBV, SerializationError> {
+        (&self.1).from_bool(value)
+    }
+    fn from_i8(&self, value: i8) -> Result<Self::DBV, SerializationError> {
+        (&self.1).from_i8(value)
+    }
+    fn from_i16(&self, value: i16) -> Result<Self::DBV, SerializationError> {
+        (&self.1).from_i16(value)
+    }
+    fn from_i32(&self, value: i32) -> Result<Self::DBV, SerializationError> {
+        (&self.1).from_i32(value)
+    }
+    fn from_i64(&self, value: i64) -> Result<Self::DBV, SerializationError> {
+        (&self.1).from_i64(value)
+    }
+    fn from_u8(&self, value: u8) -> Result<Self::DBV, SerializationError> {
+        (&self.1).from_u8(value)
+    }
+    fn from_u16(&self, value: u16) -> Result<Self::DBV, SerializationError> {
+        (&self.1).from_u16(value)
+    }
+    fn from_u32(&self, value: u32) -> Result<Self::DBV, SerializationError> {
+        (&self.1).from_u32(value)
+    }
+    fn from_u64(&self, value: u64) -> Result<Self::DBV, SerializationError> {
+        (&self.1).from_u64(value)
+    }
+    fn from_f32(&self, value: f32) -> Result<Self::DBV, SerializationError> {
+        (&self.1).from_f32(value)
+    }
+    fn from_f64(&self, value: f64) -> Result<Self::DBV, SerializationError> {
+        (&self.1).from_f64(value)
+    }
+    fn from_char(&self, value: char) -> Result<Self::DBV, SerializationError> {
+        (&self.1).from_char(value)
+    }
+    fn from_str(&self, value: &str) -> Result<Self::DBV, SerializationError> {
+        (&self.1).from_str(value)
+    }
+    fn from_bytes(&self, value: &[u8]) -> Result<Self::DBV, SerializationError> {
+        (&self.1).from_bytes(value)
+    }
+    fn from_none(&self) -> Result<Self::DBV, SerializationError> {
+        (&self.1).from_none()
+    }
+    fn descriptor(&self) -> String {
+        (&self.1).descriptor()
+    }
+    fn has_default(&self) -> bool {
+        (&self.1).has_default()
+    }
+    fn name(&self) -> Option<&str> {
+        Some(self.0)
+    }
 }
 
 fn mock_db_timestamp(value: &str) -> Result<mock_db::Timestamp, SerializationError> {
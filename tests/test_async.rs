@@ -0,0 +1,131 @@
+#![cfg(feature = "async")]
+
+extern crate chrono;
+extern crate flexi_logger;
+extern crate futures;
+#[macro_use]
+extern crate log;
+extern crate serde;
+extern crate serde_db;
+#[macro_use]
+extern crate serde_derive;
+
+mod mock_db;
+mod util;
+
+use crate::mock_db::{AsyncResultset, MValue, Resultset};
+use flexi_logger::ReconfigurationHandle;
+use futures::executor::block_on;
+use futures::stream::StreamExt;
+use serde_db::de::{AsyncDeserializableResultset, DeserializationOptions, RowCardinality};
+
+#[test] // cargo test --features async --test test_async -- --nocapture
+pub fn test_async() {
+    let mut loghandle = util::init_logger();
+
+    match block_on(impl_test_async(&mut loghandle)) {
+        Err(e) => {
+            error!("test_async() failed with {:?}", e);
+            assert!(false)
+        }
+        Ok(_) => debug!("test_async() ended successful"),
+    }
+}
+
+#[test]
+#[should_panic(expected = "polled after completion")]
+pub fn test_async_into_typed_polled_after_completion() {
+    block_on(async {
+        let mut future = get_resultset(1).into_typed::<TestData>();
+        (&mut future).await.unwrap();
+        futures::poll!(&mut future).is_ready()
+    });
+}
+
+#[derive(Debug, Deserialize)]
+struct TestData {
+    f1: String,
+    f2: i32,
+}
+
+#[derive(Debug, Deserialize)]
+struct ShortData {
+    f2: i32,
+}
+
+async fn impl_test_async(loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    info!("=== Async resultsets ===");
+    into_typed_stream(loghandle).await?;
+    into_typed(loghandle).await?;
+    next_row(loghandle).await?;
+    Ok(())
+}
+
+fn get_resultset(size: i16) -> AsyncResultset {
+    let mut rs = Resultset::new(&["f1", "f2"]);
+    for i in 1..=size {
+        rs.push(vec![MValue::String(format!("row {}", i)), MValue::Short(i)]);
+    }
+    AsyncResultset::new(rs)
+}
+
+async fn into_typed_stream(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    info!("Stream of typed rows");
+    let mut stream = get_resultset(10).into_typed_stream::<TestData>();
+    let mut sum = 0;
+    while let Some(td) = stream.next().await {
+        sum += td?.f2;
+    }
+    assert_eq!(sum, 55);
+
+    info!("Stream of typed rows, with options");
+    let options = DeserializationOptions::default().ignore_unknown_columns(true);
+    let mut stream = get_resultset(10).into_typed_stream_with::<ShortData>(&options);
+    let mut sum = 0;
+    while let Some(sd) = stream.next().await {
+        sum += sd?.f2;
+    }
+    assert_eq!(sum, 55);
+
+    let s = "Negative test: conversion error in a single row of the stream";
+    info!("{}", s);
+    let results: Vec<mock_db::Result<(i32, i32)>> =
+        get_resultset(3).into_typed_stream().collect().await;
+    assert_eq!(results.len(), 3);
+    assert!(results.iter().all(Result::is_err), "Failed \"{}\"", s);
+    Ok(())
+}
+
+async fn into_typed(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    info!("Convert the complete resultset");
+    let data: Vec<TestData> = get_resultset(5).into_typed().await?;
+    assert_eq!(data.len(), 5);
+    assert_eq!(data[4].f1, "row 5");
+
+    let data: TestData = get_resultset(1).into_typed().await?;
+    assert_eq!(data.f2, 1);
+
+    info!("Convert the complete resultset, with options");
+    let options = DeserializationOptions::default().cardinality(RowCardinality::FirstRow);
+    let data: TestData = get_resultset(3).into_typed_with(&options).await?;
+    assert_eq!(data.f2, 1);
+
+    let s = "Negative test: no conversion of a resultset with multiple rows into a struct";
+    info!("{}", s);
+    let result: mock_db::Result<TestData> = get_resultset(2).into_typed().await;
+    assert!(result.is_err(), "Failed \"{}\"", s);
+    Ok(())
+}
+
+async fn next_row(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    info!("Fetch rows one by one");
+    let mut rs = get_resultset(2);
+    let mut count = 0;
+    while let Some(row) = rs.next().await? {
+        let td: TestData = row.try_into()?;
+        count += 1;
+        assert_eq!(td.f2, count);
+    }
+    assert_eq!(count, 2);
+    Ok(())
+}
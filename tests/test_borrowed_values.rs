@@ -0,0 +1,84 @@
+extern crate chrono;
+extern crate flexi_logger;
+#[macro_use]
+extern crate log;
+extern crate serde;
+extern crate serde_db;
+#[macro_use]
+extern crate serde_derive;
+
+mod mock_db;
+mod util;
+
+use crate::mock_db::{MValue, Resultset};
+use flexi_logger::ReconfigurationHandle;
+use std::borrow::Cow;
+
+#[test] // cargo test --test test_borrowed_values -- --nocapture
+pub fn test_borrowed_values() {
+    let mut loghandle = util::init_logger();
+
+    match impl_test_borrowed_values(&mut loghandle) {
+        Err(e) => {
+            error!("test_borrowed_values() failed with {:?}", e);
+            assert!(false)
+        }
+        Ok(_) => debug!("test_borrowed_values() ended successful"),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Borrowing<'a> {
+    name: &'a str,
+    #[serde(borrow)]
+    city: Cow<'a, str>,
+    data: &'a [u8],
+}
+
+fn impl_test_borrowed_values(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    info!("Convert borrowed values into a struct with &str, Cow<str>, and &[u8] fields");
+    let v: Vec<Borrowing> = get_resultset(MValue::BorrowedString("Berlin")).try_into()?;
+    assert_eq!(v.len(), 2);
+    assert_eq!(v[1].name, "name_1");
+    assert_eq!(v[1].data, b"data");
+    match v[1].city {
+        Cow::Borrowed(city) => assert_eq!(city, "Berlin"),
+        Cow::Owned(_) => assert!(false, "Cow::Owned for a borrowed value"),
+    }
+
+    info!("Convert an owned value into a Cow<str>");
+    let v: Vec<Borrowing> = get_resultset(MValue::String("Rome".to_string())).try_into()?;
+    match v[0].city {
+        Cow::Owned(ref city) => assert_eq!(city, "Rome"),
+        Cow::Borrowed(_) => assert!(false, "Cow::Borrowed for an owned value"),
+    }
+
+    info!("Convert a borrowed value into a &str");
+    let mut rs = Resultset::new(&["name"]);
+    rs.push(vec![MValue::BorrowedString("single")]);
+    let s: &str = rs.try_into()?;
+    assert_eq!(s, "single");
+
+    let s = "Negative test: no conversion of an owned value into a &str";
+    info!("{}", s);
+    let mut rs = Resultset::new(&["name"]);
+    rs.push(vec![MValue::String("owned".to_string())]);
+    let test: mock_db::Result<&str> = rs.try_into();
+    match test {
+        Ok(_) => assert!(false, "Failed \"{}\"", s),
+        Err(e) => info!("--> Exception: {:?}", e),
+    }
+    Ok(())
+}
+
+fn get_resultset(city: MValue) -> Resultset {
+    let mut rs = Resultset::new(&["name", "city", "data"]);
+    for name in &["name_0", "name_1"] {
+        rs.push(vec![
+            MValue::BorrowedString(name),
+            city.clone(),
+            MValue::BorrowedBytes(b"data"),
+        ]);
+    }
+    rs
+}
@@ -0,0 +1,207 @@
+extern crate flexi_logger;
+#[macro_use]
+extern crate log;
+extern crate serde_db;
+
+mod util;
+
+use flexi_logger::ReconfigurationHandle;
+use serde_db::de::{
+    CanonicalDbValue, CanonicalValue, ConversionError, ConversionPolicy, DbValue, DbValueInto,
+    DeserializableRow, DeserializationError, DeserializationOptions,
+};
+use std::collections::VecDeque;
+
+#[test] // cargo test --test test_canonical_values -- --nocapture
+pub fn test_canonical_values() {
+    let mut loghandle = util::init_logger();
+
+    match impl_test_canonical_values(&mut loghandle) {
+        Err(e) => {
+            error!("test_canonical_values() failed with {:?}", e);
+            assert!(false)
+        }
+        Ok(_) => debug!("test_canonical_values() ended successful"),
+    }
+}
+
+// A value type that only provides its canonical representation
+#[derive(Clone, Debug)]
+enum CValue {
+    Null,
+    TinyInt(u8),
+    BigInt(i64),
+    Double(f64),
+    Decimal(String),
+    Text(String),
+    Blob(Vec<u8>),
+}
+
+impl CanonicalDbValue for CValue {
+    fn into_canonical(self) -> Result<CanonicalValue, ConversionError> {
+        Ok(match self {
+            CValue::Null => CanonicalValue::Null,
+            CValue::TinyInt(u) => CanonicalValue::U64(u64::from(u)),
+            CValue::BigInt(i) => CanonicalValue::I64(i),
+            CValue::Double(f) => CanonicalValue::F64(f),
+            CValue::Decimal(s) => CanonicalValue::Decimal(s),
+            CValue::Text(s) => CanonicalValue::String(s),
+            CValue::Blob(b) => CanonicalValue::Bytes(b),
+        })
+    }
+}
+
+impl DbValue for CValue {
+    fn is_null(&self) -> bool {
+        matches!(*self, CValue::Null)
+    }
+
+    fn is_integer(&self) -> bool {
+        matches!(*self, CValue::TinyInt(_) | CValue::BigInt(_))
+    }
+
+    fn canonical(&self) -> Option<CanonicalValue> {
+        self.clone().into_canonical().ok()
+    }
+}
+
+// A row of CValues
+struct CRow {
+    names: Vec<String>,
+    values: VecDeque<CValue>,
+}
+
+impl CRow {
+    fn new(values: Vec<CValue>) -> CRow {
+        CRow {
+            names: (0..values.len()).map(|i| format!("col_{}", i)).collect(),
+            values: values.into(),
+        }
+    }
+}
+
+impl DeserializableRow for CRow {
+    type E = DeserializationError;
+    type V = CValue;
+
+    fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    fn next(&mut self) -> Option<CValue> {
+        self.values.pop_front()
+    }
+
+    fn number_of_fields(&self) -> usize {
+        self.names.len()
+    }
+
+    fn fieldname(&self, field_idx: usize) -> Option<&String> {
+        self.names.get(field_idx)
+    }
+}
+
+fn impl_test_canonical_values(
+    _loghandle: &mut ReconfigurationHandle,
+) -> Result<(), DeserializationError> {
+    info!("Convert values with the conversions that are derived from the canonical values");
+    assert_eq!(CValue::TinyInt(200).into_typed::<i16>()?, 200);
+    assert_eq!(CValue::BigInt(-5).into_typed::<i8>()?, -5);
+    assert_eq!(CValue::BigInt(70_000).into_typed::<f32>()?, 70_000.0);
+    assert_eq!(CValue::Double(42.0).into_typed::<u32>()?, 42);
+    assert_eq!(CValue::Decimal("1234.00".to_string()).into_typed::<u16>()?, 1234);
+    assert_eq!(CValue::Decimal("-12.5".to_string()).into_typed::<f64>()?, -12.5);
+    assert!(CValue::BigInt(1).into_typed::<bool>()?);
+    assert_eq!(CValue::Double(2.5).into_typed::<String>()?, "2.5");
+    assert_eq!(CValue::Blob(b"abc".to_vec()).into_typed::<String>()?, "abc");
+    let bytes: Vec<u8> = DbValueInto::try_into(CValue::Text("abc".to_string()))?;
+    assert_eq!(bytes, b"abc");
+    assert_eq!(CValue::Null.into_typed::<Option<i32>>()?, None);
+    assert_eq!(CValue::BigInt(7).into_typed::<Option<i32>>()?, Some(7));
+
+    info!("Negative tests: conversions that exceed the number range");
+    for (value, result) in [
+        (CValue::TinyInt(200), CValue::TinyInt(200).into_typed::<i8>().map(|_| ())),
+        (CValue::BigInt(-1), CValue::BigInt(-1).into_typed::<u64>().map(|_| ())),
+        (CValue::Double(1e39), CValue::Double(1e39).into_typed::<f32>().map(|_| ())),
+        (
+            CValue::Decimal("99999".to_string()),
+            CValue::Decimal("99999".to_string()).into_typed::<i16>().map(|_| ()),
+        ),
+    ] {
+        match result {
+            Err(DeserializationError::Located { cause, .. }) => match *cause {
+                DeserializationError::ConversionError(ConversionError::NumberRange(_)) => {
+                    info!("--> Exception for {:?}: {:?}", value, cause)
+                }
+                _ => assert!(false, "No NumberRange error for {:?}", value),
+            },
+            _ => assert!(false, "No NumberRange error for {:?}", value),
+        }
+    }
+
+    info!("Negative tests: conversions between non-matching types");
+    assert!(CValue::Double(1.5).into_typed::<i32>().is_err());
+    assert!(CValue::Text("abc".to_string()).into_typed::<i32>().is_err());
+    assert!(CValue::Blob(vec![1, 2]).into_typed::<f64>().is_err());
+    assert!(CValue::Null.into_typed::<i32>().is_err());
+
+    info!("Apply the conversion policy of the deserialization options");
+    let options = DeserializationOptions::default();
+    let t: (i16, f32, u8) = CRow::new(vec![
+        CValue::Double(-3.0),
+        CValue::BigInt(16_777_216),
+        CValue::Decimal("12.00".to_string()),
+    ])
+    .into_typed_with(&options)?;
+    assert_eq!(t, (-3, 16_777_216.0, 12));
+
+    let options = DeserializationOptions::default().conversion_policy(ConversionPolicy::Lossy);
+    let t: (i16, f32, u8) = CRow::new(vec![
+        CValue::Double(-2.5),
+        CValue::BigInt(16_777_217),
+        CValue::Decimal("12.4".to_string()),
+    ])
+    .into_typed_with(&options)?;
+    assert_eq!(t, (-3, 16_777_216.0, 12));
+
+    let options =
+        DeserializationOptions::default().conversion_policy(ConversionPolicy::ParseFromString);
+    let t: (u64, f64, bool) = CRow::new(vec![
+        CValue::Text(" 17 ".to_string()),
+        CValue::Text("-1.5".to_string()),
+        CValue::Text("true".to_string()),
+    ])
+    .into_typed_with(&options)?;
+    assert_eq!(t, (17, -1.5, true));
+
+    info!("Negative tests: conversions that the conversion policy does not allow");
+    let default = DeserializationOptions::default();
+    let strict = DeserializationOptions::default().conversion_policy(ConversionPolicy::Strict);
+    let lossy = DeserializationOptions::default().conversion_policy(ConversionPolicy::Lossy);
+    for (value, options) in [
+        (CValue::Double(2.5), &default),
+        (CValue::Text("17".to_string()), &lossy),
+        (CValue::Double(2.0), &strict),
+        (CValue::Decimal("2".to_string()), &strict),
+        (CValue::Double(1e6), &lossy),
+    ] {
+        let result: Result<i16, _> = CRow::new(vec![value.clone()]).into_typed_with(options);
+        match result {
+            Err(e) => info!("--> Exception for {:?}: {:?}", value, e),
+            Ok(_) => assert!(false, "No exception for {:?}", value),
+        }
+    }
+    for (value, options) in [
+        (CValue::BigInt(16_777_217), &default),
+        (CValue::Double(0.1), &default),
+        (CValue::BigInt(1), &strict),
+    ] {
+        let result: Result<f32, _> = CRow::new(vec![value.clone()]).into_typed_with(options);
+        match result {
+            Err(e) => info!("--> Exception for {:?}: {:?}", value, e),
+            Ok(_) => assert!(false, "No exception for {:?}", value),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,117 @@
+extern crate chrono;
+extern crate flexi_logger;
+#[macro_use]
+extern crate log;
+extern crate serde;
+extern crate serde_db;
+#[macro_use]
+extern crate serde_derive;
+
+mod mock_db;
+mod util;
+
+use crate::mock_db::{MValue, Resultset};
+use flexi_logger::ReconfigurationHandle;
+use serde_db::de::{ColumnNameMatching, DeserializationOptions};
+
+#[test] // cargo test --test test_column_names -- --nocapture
+pub fn test_column_names() {
+    let mut loghandle = util::init_logger();
+
+    match impl_test_column_names(&mut loghandle) {
+        Err(e) => {
+            error!("test_column_names() failed with {:?}", e);
+            assert!(false)
+        }
+        Ok(_) => debug!("test_column_names() ended successful"),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Address {
+    street: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Customer {
+    customer_id: i16,
+    #[serde(rename = "lastName")]
+    last_name: String,
+    address: Address,
+}
+
+fn impl_test_column_names(loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    info!("=== Column names ===");
+    case_insensitive(loghandle)?;
+    snake_case(loghandle)?;
+    custom_normalizer(loghandle)?;
+    Ok(())
+}
+
+fn get_resultset(fields: &[&'static str]) -> Resultset {
+    let mut rs = Resultset::new(fields);
+    rs.push(vec![
+        MValue::Short(7),
+        MValue::String("Miller".to_string()),
+        MValue::String("Main Street".to_string()),
+    ]);
+    rs
+}
+
+fn case_insensitive(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    info!("Match upper-case column names case-insensitively");
+    let fields = ["CUSTOMER_ID", "LASTNAME", "ADDRESS_STREET"];
+    let options = DeserializationOptions::default()
+        .column_name_matching(ColumnNameMatching::CaseInsensitive);
+    let customer: Customer = get_resultset(&fields).try_into_with(&options)?;
+    assert_eq!(customer.customer_id, 7);
+    assert_eq!(customer.last_name, "Miller");
+    assert_eq!(customer.address.street, "Main Street");
+
+    let s = "Negative test: upper-case column names do not match by default";
+    info!("{}", s);
+    let result: mock_db::Result<Customer> = get_resultset(&fields).try_into();
+    assert!(result.is_err(), "Failed \"{}\"", s);
+    Ok(())
+}
+
+fn snake_case(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    info!("Convert UPPER_SNAKE_CASE column names to snake_case");
+    let options =
+        DeserializationOptions::default().column_name_matching(ColumnNameMatching::SnakeCase);
+    let mut rs = Resultset::new(&["CUSTOMER_ID", "ADDRESS_STREET"]);
+    rs.push(vec![MValue::Short(7), MValue::String("Main Street".to_string())]);
+    let (id, address): (i16, Address) = rs.try_into_with(&options)?;
+    assert_eq!(id, 7);
+    assert_eq!(address.street, "Main Street");
+
+    info!("Convert CamelCase and lowerCamelCase column names to snake_case");
+    #[derive(Debug, Deserialize)]
+    struct Person {
+        customer_id: i16,
+        last_name: String,
+        address: Address,
+    }
+    for fields in &[
+        ["CustomerId", "LastName", "Address.Street"],
+        ["customerId", "lastName", "address.street"],
+        ["CUSTOMERId", "LAST_NAME", "ADDRESS_STREET"],
+    ] {
+        let person: Person = get_resultset(fields).try_into_with(&options)?;
+        assert_eq!(person.customer_id, 7);
+        assert_eq!(person.last_name, "Miller");
+        assert_eq!(person.address.street, "Main Street");
+    }
+    Ok(())
+}
+
+fn custom_normalizer(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    info!("Convert column names with a custom function");
+    let options = DeserializationOptions::default()
+        .column_name_normalizer(|name| name.trim_start_matches("C_").to_string());
+    let rs = get_resultset(&["C_customer_id", "C_lastName", "C_address.street"]);
+    let customer: Customer = rs.try_into_with(&options)?;
+    assert_eq!(customer.customer_id, 7);
+    assert_eq!(customer.address.street, "Main Street");
+    Ok(())
+}
@@ -0,0 +1,177 @@
+#![cfg(feature = "conformance")]
+
+extern crate chrono;
+extern crate flexi_logger;
+#[macro_use]
+extern crate log;
+extern crate serde;
+extern crate serde_db;
+
+mod mock_db;
+mod util;
+
+use crate::mock_db::{MValue, ParameterType as PT, Resultset, Timestamp};
+use chrono::NaiveDate;
+use flexi_logger::ReconfigurationHandle;
+use serde_db::conformance::{check_cardinality, Outcome, ParamChecks, ValueChecks};
+use serde_db::de::{CanonicalValue, ConversionError, DbValue, DbValueInto};
+
+#[test] // cargo test --features conformance --test test_conformance -- --nocapture
+pub fn test_conformance() {
+    let mut loghandle = util::init_logger();
+    conformance_of_mock_db(&mut loghandle);
+    detection_of_failures(&mut loghandle);
+}
+
+fn conformance_of_mock_db(_loghandle: &mut ReconfigurationHandle) {
+    info!("Run the conformance checks against the mock driver");
+    let ts = NaiveDate::from_ymd(2012, 2, 2).and_hms(2, 2, 2);
+    let report = ValueChecks::new()
+        .value("Short 42", || MValue::Short(42), CanonicalValue::I64(42))
+        .value("Short -300", || MValue::Short(-300), CanonicalValue::I64(-300))
+        .value("NullableShort 7", || MValue::NullableShort(Some(7)), CanonicalValue::I64(7))
+        .value("Double 2.5", || MValue::Double(2.5), CanonicalValue::F64(2.5))
+        .value(
+            "String abc",
+            || MValue::String("abc".to_string()),
+            CanonicalValue::String("abc".to_string()),
+        )
+        .value(
+            "BorrowedString abc",
+            || MValue::BorrowedString("abc"),
+            CanonicalValue::String("abc".to_string()),
+        )
+        .value(
+            "BorrowedBytes abc",
+            || MValue::BorrowedBytes(b"abc"),
+            CanonicalValue::Bytes(b"abc".to_vec()),
+        )
+        .value(
+            "Timestamp",
+            move || MValue::Timestamp(Timestamp(ts)),
+            CanonicalValue::String(Timestamp(ts).to_string()),
+        )
+        .null("NullableShort NULL", || MValue::NullableShort(None))
+        .null("NullableString NULL", || MValue::NullableString(None))
+        .run()
+        .merge(check_cardinality(|rows| {
+            let mut rs = Resultset::new(&["id"]);
+            for i in 0..rows {
+                rs.push(vec![MValue::Short(i as i16)]);
+            }
+            rs
+        }))
+        .merge(
+            ParamChecks::new()
+                .parameter("Short", &PT::Short)
+                .parameter("NullableShort", &PT::NullableShort)
+                .parameter("String", &PT::String)
+                .parameter("NullableString", &PT::NullableString)
+                .run(),
+        );
+    info!("Report:\n{}", report);
+    report.assert_conformant();
+
+    let values = report.matrix("Values").unwrap();
+    assert_eq!(values.get("Short 42", "i64"), Some(&Outcome::Supported));
+    assert_eq!(values.get("Short 42", "canonical"), Some(&Outcome::Unsupported));
+    assert_eq!(values.get("Short 42", "u8"), Some(&Outcome::Unsupported));
+    assert_eq!(values.get("Short 42", "bool"), Some(&Outcome::Rejected));
+    assert_eq!(values.get("Double 2.5", "String"), Some(&Outcome::Supported));
+    assert_eq!(values.get("BorrowedString abc", "&str"), Some(&Outcome::Supported));
+    assert_eq!(values.get("NullableString NULL", "String"), Some(&Outcome::Rejected));
+    let params = report.matrix("Parameters").unwrap();
+    assert_eq!(params.get("Short", "i16"), Some(&Outcome::Supported));
+    assert_eq!(params.get("Short", "NULL"), Some(&Outcome::Unsupported));
+    assert_eq!(params.get("NullableShort", "NULL"), Some(&Outcome::Supported));
+    assert_eq!(params.get("String", "String"), Some(&Outcome::Supported));
+    let cardinality = report.matrix("Resultset cardinality").unwrap();
+    assert_eq!(cardinality.rows().len(), 10);
+}
+
+// A value of a defective driver, which truncates numbers that exceed the range of the rust type;
+// the deserialization does not show the defect, because it uses the canonical value
+struct TruncatingValue(i64);
+
+impl DbValue for TruncatingValue {
+    fn is_null(&self) -> bool {
+        false
+    }
+
+    fn is_integer(&self) -> bool {
+        true
+    }
+
+    fn canonical(&self) -> Option<CanonicalValue> {
+        Some(CanonicalValue::I64(self.0))
+    }
+}
+
+macro_rules! impl_truncating_into {
+    ($($t:ty),*) => {
+        $(
+            impl DbValueInto<$t> for TruncatingValue {
+                fn try_into(self) -> Result<$t, ConversionError> {
+                    Ok(self.0 as $t)
+                }
+            }
+        )*
+    };
+}
+
+impl_truncating_into!(u8, u16, u32, u64, i8, i16, i32, i64, f32, f64);
+
+impl DbValueInto<bool> for TruncatingValue {
+    fn try_into(self) -> Result<bool, ConversionError> {
+        Err(ConversionError::ValueType("no bool".to_string()))
+    }
+}
+impl DbValueInto<String> for TruncatingValue {
+    fn try_into(self) -> Result<String, ConversionError> {
+        Ok(self.0.to_string())
+    }
+}
+impl DbValueInto<Vec<u8>> for TruncatingValue {
+    fn try_into(self) -> Result<Vec<u8>, ConversionError> {
+        Err(ConversionError::ValueType("no bytes".to_string()))
+    }
+}
+
+fn detection_of_failures(_loghandle: &mut ReconfigurationHandle) {
+    info!("Negative test: wrong values and NULL handling are reported as failures");
+    let report = ValueChecks::new()
+        .value("Short 42", || MValue::Short(42), CanonicalValue::I64(43))
+        .value("Short as U64", || MValue::Short(42), CanonicalValue::U64(42))
+        .null("Short 0", || MValue::Short(0))
+        .run();
+    info!("Report:\n{}", report);
+    assert!(!report.is_conformant());
+
+    let values = report.matrix("Values").unwrap();
+    assert!(values.get("Short 42", "i64").unwrap().is_failed());
+    assert!(values.get("Short as U64", "u64").unwrap().is_failed());
+    assert_eq!(values.get("Short as U64", "u8"), Some(&Outcome::Unsupported));
+    assert!(values.get("Short 0", "is_null").unwrap().is_failed());
+    assert!(values.get("Short 0", "i32").unwrap().is_failed());
+    for failure in report.failures() {
+        info!("--> Failure: {}", failure);
+    }
+
+    info!("Negative test: values that exceed the range of the rust type must not be converted");
+    let report = ValueChecks::new()
+        .value("Truncating 300", || TruncatingValue(300), CanonicalValue::I64(300))
+        .value("Truncating -1", || TruncatingValue(-1), CanonicalValue::I64(-1))
+        .run();
+    info!("Report:\n{}", report);
+    assert!(!report.is_conformant());
+
+    let values = report.matrix("Values").unwrap();
+    assert_eq!(values.get("Truncating 300", "canonical"), Some(&Outcome::Supported));
+    assert_eq!(values.get("Truncating 300", "i16"), Some(&Outcome::Supported));
+    assert!(values.get("Truncating 300", "u8").unwrap().is_failed());
+    assert!(values.get("Truncating 300", "i8").unwrap().is_failed());
+    assert!(values.get("Truncating -1", "u64").unwrap().is_failed());
+    for failure in report.failures() {
+        info!("--> Failure: {}", failure);
+    }
+}
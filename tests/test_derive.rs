@@ -0,0 +1,162 @@
+extern crate flexi_logger;
+#[macro_use]
+extern crate log;
+extern crate serde_db;
+#[macro_use]
+extern crate serde_db_derive;
+
+mod util;
+
+use flexi_logger::ReconfigurationHandle;
+use serde_db::de::{CanonicalValue, ConversionError, DbValue, DeserializationError};
+use std::fmt;
+
+#[test] // cargo test --test test_derive -- --nocapture
+pub fn test_derive() {
+    let mut loghandle = util::init_logger();
+
+    match impl_test_derive(&mut loghandle) {
+        Err(e) => {
+            error!("test_derive() failed with {:?}", e);
+            assert!(false)
+        }
+        Ok(_) => debug!("test_derive() ended successful"),
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Timestamp(u32, u32);
+impl fmt::Display for Timestamp {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{:02}:{:02}", self.0, self.1)
+    }
+}
+
+type DbInt = i32;
+type NullableDbInt = Option<i32>;
+
+// A wrapper that is converted with `Into`
+#[derive(Clone, Debug)]
+struct BigInt(i64);
+impl From<BigInt> for i64 {
+    fn from(v: BigInt) -> i64 {
+        v.0
+    }
+}
+
+#[derive(Clone, Debug)]
+struct NullableBigInt(Option<i64>);
+impl From<NullableBigInt> for Option<i64> {
+    fn from(v: NullableBigInt) -> Option<i64> {
+        v.0
+    }
+}
+
+#[derive(Clone, Debug)]
+struct LobHandle;
+
+// A value type of a driver, with the conversions derived from the variants
+#[derive(Debug, DbValue)]
+enum DValue<'a> {
+    Null,
+    TinyInt(u8),
+    Short(i16),
+    NullableShort(Option<i16>),
+    #[db_value(rust_type = "i32")]
+    Int(DbInt),
+    #[db_value(nullable, rust_type = "i32")]
+    NullableInt(NullableDbInt),
+    #[db_value(rust_type = "i64")]
+    BigInt(BigInt),
+    #[db_value(rust_type = "Option<i64>")]
+    NullableBigInt(NullableBigInt),
+    Double(f64),
+    Boolean(bool),
+    #[db_value(conversion = "decimal")]
+    Decimal(String),
+    Text(String),
+    NullableText(Option<String>),
+    BorrowedText(&'a str),
+    Blob(Vec<u8>),
+    BorrowedBlob(&'a [u8]),
+    #[db_value(conversion = "display")]
+    Time(Timestamp),
+    #[db_value(conversion = "none")]
+    Lob(LobHandle),
+}
+
+fn impl_test_derive(_loghandle: &mut ReconfigurationHandle) -> Result<(), DeserializationError> {
+    info!("Recognize NULL values");
+    assert!(DValue::Null.is_null());
+    assert!(DValue::NullableShort(None).is_null());
+    assert!(DValue::NullableInt(None).is_null());
+    assert!(DValue::NullableText(None).is_null());
+    assert!(!DValue::NullableShort(Some(0)).is_null());
+    assert!(!DValue::Text(String::new()).is_null());
+    assert!(DValue::NullableBigInt(NullableBigInt(None)).is_null());
+    assert!(!DValue::NullableBigInt(NullableBigInt(Some(0))).is_null());
+
+    info!("Recognize integer values");
+    assert!(DValue::TinyInt(7).is_integer());
+    assert!(DValue::NullableInt(Some(7)).is_integer());
+    assert!(DValue::BigInt(BigInt(7)).is_integer());
+    assert!(DValue::NullableBigInt(NullableBigInt(Some(7))).is_integer());
+    assert!(!DValue::NullableShort(None).is_integer());
+    assert!(!DValue::NullableBigInt(NullableBigInt(None)).is_integer());
+    assert!(!DValue::Double(7.0).is_integer());
+    assert!(!DValue::Text("7".to_string()).is_integer());
+
+    info!("Provide the canonical representation");
+    assert_eq!(DValue::TinyInt(7).canonical(), Some(CanonicalValue::U64(7)));
+    assert_eq!(DValue::Int(-7).canonical(), Some(CanonicalValue::I64(-7)));
+    assert_eq!(DValue::BigInt(BigInt(-7)).canonical(), Some(CanonicalValue::I64(-7)));
+    assert_eq!(
+        DValue::NullableBigInt(NullableBigInt(None)).canonical(),
+        Some(CanonicalValue::Null)
+    );
+    assert_eq!(
+        DValue::Decimal("12.50".to_string()).canonical(),
+        Some(CanonicalValue::Decimal("12.50".to_string()))
+    );
+    assert_eq!(DValue::NullableShort(None).canonical(), Some(CanonicalValue::Null));
+    assert_eq!(DValue::Lob(LobHandle).canonical(), None);
+
+    info!("Convert the values with the derived conversions");
+    assert_eq!(DValue::TinyInt(200).into_typed::<i64>()?, 200);
+    assert_eq!(DValue::Short(-5).into_typed::<i8>()?, -5);
+    assert_eq!(DValue::NullableShort(Some(3)).into_typed::<Option<u32>>()?, Some(3));
+    assert_eq!(DValue::NullableShort(None).into_typed::<Option<u32>>()?, None);
+    assert_eq!(DValue::NullableInt(Some(70_000)).into_typed::<f64>()?, 70_000.0);
+    assert_eq!(DValue::BigInt(BigInt(70_000)).into_typed::<i32>()?, 70_000);
+    assert_eq!(
+        DValue::NullableBigInt(NullableBigInt(Some(-3))).into_typed::<Option<i8>>()?,
+        Some(-3)
+    );
+    assert_eq!(DValue::NullableBigInt(NullableBigInt(None)).into_typed::<Option<i8>>()?, None);
+    assert_eq!(DValue::Double(42.0).into_typed::<u16>()?, 42);
+    assert!(DValue::Boolean(true).into_typed::<bool>()?);
+    assert_eq!(DValue::Decimal("1234.00".to_string()).into_typed::<i32>()?, 1234);
+    assert_eq!(DValue::Text("abc".to_string()).into_typed::<String>()?, "abc");
+    assert_eq!(DValue::Time(Timestamp(9, 5)).into_typed::<String>()?, "09:05");
+    assert_eq!(DValue::Blob(b"abc".to_vec()).into_typed::<String>()?, "abc");
+
+    info!("Hand out borrowed values without copying them");
+    assert_eq!(DValue::BorrowedText("abc").into_typed::<&str>()?, "abc");
+    assert_eq!(DValue::BorrowedBlob(b"abc").into_typed::<&[u8]>()?, b"abc");
+    assert_eq!(DValue::Text("abc".to_string()).as_str(), None);
+
+    info!("Negative tests");
+    assert!(DValue::Short(-1).into_typed::<u8>().is_err());
+    assert!(DValue::Double(1.5).into_typed::<i32>().is_err());
+    assert!(DValue::Null.into_typed::<i32>().is_err());
+    match DValue::Lob(LobHandle).into_typed::<String>() {
+        Err(DeserializationError::Located { cause, .. }) => match *cause {
+            DeserializationError::ConversionError(ConversionError::ValueType(s)) => {
+                info!("--> Exception: {}", s)
+            }
+            cause => assert!(false, "Unexpected error {:?}", cause),
+        },
+        result => assert!(false, "Unexpected result {:?}", result),
+    }
+    Ok(())
+}
@@ -0,0 +1,196 @@
+extern crate chrono;
+extern crate flexi_logger;
+#[macro_use]
+extern crate log;
+extern crate serde;
+extern crate serde_db;
+#[macro_use]
+extern crate serde_derive;
+
+mod mock_db;
+mod util;
+
+use crate::mock_db::{MValue, ParameterType as PT, Resultset};
+use flexi_logger::ReconfigurationHandle;
+
+#[test] // cargo test --test test_enums -- --nocapture
+pub fn test_enums() {
+    let mut loghandle = util::init_logger();
+
+    match impl_test_enums(&mut loghandle) {
+        Err(e) => {
+            error!("test_enums() failed with {:?}", e);
+            assert!(false)
+        }
+        Ok(_) => debug!("test_enums() ended successful"),
+    }
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+enum Status {
+    Open,
+    #[serde(rename = "IN_PROGRESS")]
+    InProgress,
+    Closed,
+}
+
+#[derive(Debug, Serialize)]
+enum Shape {
+    Circle(i16),
+    Square(i16),
+    Rectangle { width: i16, height: i16 },
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Ticket {
+    id: i32,
+    status: Status,
+    prev_status: Option<Status>,
+}
+
+fn impl_test_enums(loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    info!("=== Enums ===");
+    enums_from_names(loghandle)?;
+    enums_from_indices(loghandle)?;
+    not_enum_from_unknown_name(loghandle)?;
+    enums_into_params(loghandle)?;
+    not_data_variants_into_params(loghandle)?;
+    Ok(())
+}
+
+fn enums_from_names(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    info!("Deserialize enums from string columns, using the variant names");
+    let mut rs = Resultset::new(&["id", "status", "prev_status"]);
+    rs.push(vec![
+        MValue::Short(1),
+        MValue::String("IN_PROGRESS".to_string()),
+        MValue::NullableString(None),
+    ]);
+    rs.push(vec![
+        MValue::Short(2),
+        MValue::String("Closed".to_string()),
+        MValue::NullableShort(None),
+    ]);
+    let tickets: Vec<Ticket> = rs.try_into()?;
+    assert_eq!(tickets[0].id, 1);
+    assert_eq!(tickets[0].status, Status::InProgress);
+    assert_eq!(tickets[1].status, Status::Closed);
+    assert_eq!(tickets[1].prev_status, None);
+
+    let mut rs = Resultset::new(&["status"]);
+    rs.push(vec![MValue::String("Open".to_string())]);
+    let status: Status = rs.try_into()?;
+    assert_eq!(status, Status::Open);
+    Ok(())
+}
+
+fn enums_from_indices(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    info!("Deserialize enums from integer columns, using the variant indices");
+    let mut rs = Resultset::new(&["id", "status", "prev_status"]);
+    rs.push(vec![
+        MValue::Short(1),
+        MValue::Short(2),
+        MValue::NullableShort(Some(1)),
+    ]);
+    for row in rs {
+        let ticket: Ticket = row.try_into()?;
+        assert_eq!(ticket.status, Status::Closed);
+        assert_eq!(ticket.prev_status, Some(Status::InProgress));
+    }
+    Ok(())
+}
+
+fn not_enum_from_unknown_name(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    let s = "Negative test: no conversion of unknown name into enum";
+    info!("{}", s);
+    let mut rs = Resultset::new(&["status"]);
+    rs.push(vec![MValue::String("Reopened".to_string())]);
+    let test: mock_db::Result<Status> = rs.try_into();
+    match test {
+        Ok(_) => assert!(false, "Failed \"{}\"", s),
+        Err(e) => info!("--> Exception: {:?}", e),
+    }
+
+    let s = "Negative test: no conversion of numeric string into enum";
+    info!("{}", s);
+    let mut rs = Resultset::new(&["status"]);
+    rs.push(vec![MValue::String("1".to_string())]);
+    let test: mock_db::Result<Status> = rs.try_into();
+    match test {
+        Ok(_) => assert!(false, "Failed \"{}\"", s),
+        Err(e) => info!("--> Exception: {:?}", e),
+    }
+
+    let s = "Negative test: no conversion of unknown index into enum";
+    info!("{}", s);
+    let mut rs = Resultset::new(&["status"]);
+    rs.push(vec![MValue::Short(3)]);
+    let test: mock_db::Result<Status> = rs.try_into();
+    match test {
+        Ok(_) => assert!(false, "Failed \"{}\"", s),
+        Err(e) => info!("--> Exception: {:?}", e),
+    }
+    Ok(())
+}
+
+fn enums_into_params(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    info!("Serialize enums into parameters, and read them back");
+    let ticket = Ticket {
+        id: 7,
+        status: Status::InProgress,
+        prev_status: Some(Status::Open),
+    };
+
+    let input_metadata = vec![PT::Short, PT::String, PT::NullableShort];
+    let params: Vec<MValue> = serde_db::ser::to_params(&ticket, &mut input_metadata.iter())?;
+    assert_eq!(
+        params,
+        vec![
+            MValue::Short(7),
+            MValue::String("IN_PROGRESS".to_string()),
+            MValue::NullableShort(Some(0)),
+        ]
+    );
+
+    let mut rs = Resultset::new(&["id", "status", "prev_status"]);
+    rs.push(params);
+    let read_back: Ticket = rs.try_into()?;
+    assert_eq!(read_back.id, ticket.id);
+    assert_eq!(read_back.status, ticket.status);
+    assert_eq!(read_back.prev_status, ticket.prev_status);
+
+    let s = "Negative test: no serialization of enum into timestamp";
+    info!("{}", s);
+    let input_metadata = vec![PT::Timestamp];
+    let test: mock_db::Result<Vec<MValue>> =
+        serde_db::ser::to_params(&Status::Closed, &mut input_metadata.iter())
+            .map_err(mock_db::Error::from);
+    match test {
+        Ok(_) => assert!(false, "Failed \"{}\"", s),
+        Err(e) => info!("--> Exception: {:?}", e),
+    }
+    Ok(())
+}
+
+fn not_data_variants_into_params(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    let s = "Negative test: no serialization of enum variants with data";
+    info!("{}", s);
+    for shape in &[
+        Shape::Circle(5),
+        Shape::Square(5),
+        Shape::Rectangle {
+            width: 5,
+            height: 5,
+        },
+    ] {
+        let input_metadata = [PT::Short, PT::Short];
+        let test: mock_db::Result<Vec<MValue>> =
+            serde_db::ser::to_params(shape, &mut input_metadata.iter())
+                .map_err(mock_db::Error::from);
+        match test {
+            Ok(_) => assert!(false, "Failed \"{}\" for {:?}", s, shape),
+            Err(e) => info!("--> Exception: {:?}", e),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,391 @@
+extern crate chrono;
+extern crate flexi_logger;
+#[macro_use]
+extern crate log;
+extern crate serde;
+extern crate serde_db;
+#[macro_use]
+extern crate serde_derive;
+
+mod mock_db;
+mod util;
+
+use crate::mock_db::{MValue, Resultset};
+use flexi_logger::ReconfigurationHandle;
+use serde_db::de::{ColumnNameMatching, DeserializationError, DeserializationOptions};
+use std::collections::BTreeMap;
+
+#[test] // cargo test --test test_nested_structs -- --nocapture
+pub fn test_nested_structs() {
+    let mut loghandle = util::init_logger();
+
+    match impl_test_nested_structs(&mut loghandle) {
+        Err(e) => {
+            error!("test_nested_structs() failed with {:?}", e);
+            assert!(false)
+        }
+        Ok(_) => debug!("test_nested_structs() ended successful"),
+    }
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Geo {
+    lat: i16,
+    lon: i16,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Address {
+    street: String,
+    geo: Geo,
+}
+
+#[derive(Debug, Deserialize)]
+struct Customer {
+    id: i16,
+    address: Address,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct User {
+    id: i16,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Order {
+    id: i16,
+    amount: i16,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Item {
+    pos: i16,
+    article: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OrderWithItems {
+    id: i16,
+    customer: String,
+    items: Vec<Item>,
+}
+
+fn impl_test_nested_structs(loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    info!("=== Nested structs ===");
+    nested_struct(loghandle)?;
+    nested_struct_with_separator(loghandle)?;
+    tuple_of_structs(loghandle)?;
+    tuple_of_structs_with_prefixes(loghandle)?;
+    grouped_rows(loghandle)?;
+    nested_map(loghandle)?;
+    optional_nested_struct(loghandle)?;
+    not_nested_value(loghandle)?;
+    Ok(())
+}
+
+fn nested_struct(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    info!("Fill nested structs from columns with the field name as prefix");
+    let mut rs = Resultset::new(&[
+        "id",
+        "address.street",
+        "address.geo_lat",
+        "address.geo_lon",
+        "name",
+    ]);
+    rs.push(vec![
+        MValue::Short(1),
+        MValue::String("Main Street".to_string()),
+        MValue::Short(48),
+        MValue::Short(8),
+        MValue::String("Alice".to_string()),
+    ]);
+    rs.push(vec![
+        MValue::Short(2),
+        MValue::String("Side Street".to_string()),
+        MValue::Short(49),
+        MValue::Short(9),
+        MValue::String("Bob".to_string()),
+    ]);
+    let customers: Vec<Customer> = rs.try_into()?;
+    assert_eq!(customers.len(), 2);
+    assert_eq!(customers[0].id, 1);
+    assert_eq!(customers[0].address.street, "Main Street");
+    assert_eq!(customers[0].address.geo, Geo { lat: 48, lon: 8 });
+    assert_eq!(customers[0].name, "Alice");
+    assert_eq!(customers[1].address.geo, Geo { lat: 49, lon: 9 });
+    assert_eq!(customers[1].name, "Bob");
+    Ok(())
+}
+
+fn nested_struct_with_separator(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    info!("Fill nested structs using a configured separator");
+    let options = DeserializationOptions::default().nesting_separator("__");
+    let mut rs = Resultset::new(&[
+        "id",
+        "address__street",
+        "address__geo__lat",
+        "address__geo__lon",
+        "name",
+    ]);
+    rs.push(vec![
+        MValue::Short(1),
+        MValue::String("Main Street".to_string()),
+        MValue::Short(48),
+        MValue::Short(8),
+        MValue::String("Alice".to_string()),
+    ]);
+    let customer: Customer = rs.try_into_with(&options)?;
+    assert_eq!(customer.address.geo, Geo { lat: 48, lon: 8 });
+
+    let s = "Negative test: separator is not accepted if another one is configured";
+    info!("{}", s);
+    let mut rs = Resultset::new(&[
+        "id",
+        "address_street",
+        "address_geo_lat",
+        "address_geo_lon",
+        "name",
+    ]);
+    rs.push(vec![
+        MValue::Short(1),
+        MValue::String("Main Street".to_string()),
+        MValue::Short(48),
+        MValue::Short(8),
+        MValue::String("Alice".to_string()),
+    ]);
+    let result: mock_db::Result<Customer> = rs.try_into_with(&options);
+    assert!(result.is_err(), "Failed \"{}\"", s);
+    Ok(())
+}
+
+fn tuple_of_structs(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    info!("Convert rows into tuples of structs");
+    let mut rs = Resultset::new(&["user_id", "user_name", "order_id", "order_amount"]);
+    rs.push(vec![
+        MValue::Short(1),
+        MValue::String("Alice".to_string()),
+        MValue::Short(10),
+        MValue::Short(100),
+    ]);
+    rs.push(vec![
+        MValue::Short(2),
+        MValue::String("Bob".to_string()),
+        MValue::Short(20),
+        MValue::Short(200),
+    ]);
+    let data: Vec<(User, Order)> = rs.try_into()?;
+    assert_eq!(data[0].0.name, "Alice");
+    assert_eq!(data[0].1.id, 10);
+    assert_eq!(data[1].0.id, 2);
+    assert_eq!(data[1].1.amount, 200);
+
+    info!("Convert rows into tuples of structs and plain values, split by field names");
+    let mut rs = Resultset::new(&["id", "name", "id", "amount", "comment"]);
+    rs.push(vec![
+        MValue::Short(1),
+        MValue::String("Alice".to_string()),
+        MValue::Short(10),
+        MValue::Short(100),
+        MValue::String("urgent".to_string()),
+    ]);
+    let (user, order, comment): (User, Order, String) = rs.try_into()?;
+    assert_eq!(user.id, 1);
+    assert_eq!(order.id, 10);
+    assert_eq!(order.amount, 100);
+    assert_eq!(comment, "urgent");
+    Ok(())
+}
+
+fn tuple_of_structs_with_prefixes(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    info!("Convert rows into tuples of structs, using configured prefixes");
+    let options = DeserializationOptions::default().tuple_prefixes(vec!["u", "o"]);
+    let mut rs = Resultset::new(&["uid", "uname", "oid", "oamount"]);
+    rs.push(vec![
+        MValue::Short(1),
+        MValue::String("Alice".to_string()),
+        MValue::Short(10),
+        MValue::Short(100),
+    ]);
+    let (user, order): (User, Order) = rs.try_into_with(&options)?;
+    assert_eq!(user.id, 1);
+    assert_eq!(user.name, "Alice");
+    assert_eq!(order.id, 10);
+    assert_eq!(order.amount, 100);
+    Ok(())
+}
+
+fn grouped_rows(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    info!("Group consecutive rows into parent structs with a Vec of child structs");
+    let mut rs = Resultset::new(&["id", "customer", "items_pos", "items_article"]);
+    for (id, customer, pos, article) in &[
+        (1, "Alice", 1, "apple"),
+        (1, "Alice", 2, "banana"),
+        (1, "Alice", 3, "cherry"),
+        (2, "Bob", 1, "date"),
+        (3, "Alice", 1, "elderberry"),
+        (3, "Alice", 2, "fig"),
+    ] {
+        rs.push(vec![
+            MValue::Short(*id),
+            MValue::String(customer.to_string()),
+            MValue::Short(*pos),
+            MValue::String(article.to_string()),
+        ]);
+    }
+    let orders: Vec<OrderWithItems> = rs.try_into_grouped(&["id"])?;
+    assert_eq!(orders.len(), 3);
+    assert_eq!(orders[0].id, 1);
+    assert_eq!(orders[0].customer, "Alice");
+    assert_eq!(orders[0].items.len(), 3);
+    assert_eq!(
+        orders[0].items[2],
+        Item {
+            pos: 3,
+            article: "cherry".to_string()
+        }
+    );
+    assert_eq!(orders[1].customer, "Bob");
+    assert_eq!(orders[1].items.len(), 1);
+    assert_eq!(orders[2].id, 3);
+    assert_eq!(orders[2].items[1].article, "fig");
+
+    info!("Group rows with key columns that are matched case-insensitively");
+    let options = DeserializationOptions::default()
+        .column_name_matching(ColumnNameMatching::CaseInsensitive);
+    let mut rs = Resultset::new(&["ID", "CUSTOMER", "ITEMS_POS", "ITEMS_ARTICLE"]);
+    for (id, pos) in &[(1, 1), (1, 2), (2, 1)] {
+        rs.push(vec![
+            MValue::Short(*id),
+            MValue::String("Alice".to_string()),
+            MValue::Short(*pos),
+            MValue::String("apple".to_string()),
+        ]);
+    }
+    let orders: Vec<OrderWithItems> = rs.try_into_grouped_with(&["id"], &options)?;
+    assert_eq!(orders.len(), 2);
+    assert_eq!(orders[0].items.len(), 2);
+    assert_eq!(orders[1].id, 2);
+
+    let s = "Negative test: conversion error in a following row of a group";
+    info!("{}", s);
+    let mut rs = Resultset::new(&["id", "customer", "items_pos", "items_article"]);
+    rs.push(vec![
+        MValue::Short(1),
+        MValue::String("Alice".to_string()),
+        MValue::Short(1),
+        MValue::String("apple".to_string()),
+    ]);
+    rs.push(vec![
+        MValue::Short(1),
+        MValue::String("Alice".to_string()),
+        MValue::NullableShort(None),
+        MValue::String("banana".to_string()),
+    ]);
+    let result: mock_db::Result<Vec<OrderWithItems>> = rs.try_into_grouped(&["id"]);
+    match result {
+        Err(e) => info!("--> Exception: {}", e),
+        Ok(_) => assert!(false, "Failed \"{}\"", s),
+    }
+    let rs = Resultset::new(&["id", "customer", "items_pos", "items_article"]);
+    let result: mock_db::Result<Vec<OrderWithItems>> = rs.try_into_grouped(&["order_id"]);
+    assert!(result.is_err(), "Failed \"{}\"", s);
+    Ok(())
+}
+
+fn nested_map(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    info!("Fill a nested map from columns with the field name as prefix");
+    #[derive(Debug, Deserialize)]
+    struct Product {
+        id: i16,
+        attributes: BTreeMap<String, String>,
+        name: String,
+    }
+    let mut rs = Resultset::new(&["id", "attributes_color", "attributes_size", "name"]);
+    rs.push(vec![
+        MValue::Short(1),
+        MValue::String("red".to_string()),
+        MValue::String("XL".to_string()),
+        MValue::String("shirt".to_string()),
+    ]);
+    let product: Product = rs.try_into()?;
+    assert_eq!(product.id, 1);
+    assert_eq!(product.attributes.len(), 2);
+    assert_eq!(product.attributes["color"], "red");
+    assert_eq!(product.attributes["size"], "XL");
+    assert_eq!(product.name, "shirt");
+    Ok(())
+}
+
+fn optional_nested_struct(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    info!("Fill an optional nested struct, which is None if all its columns are NULL");
+    #[derive(Debug, Deserialize)]
+    struct Person {
+        id: i16,
+        address: Option<Geo>,
+        name: Option<String>,
+    }
+    let mut rs = Resultset::new(&["id", "address.lat", "address.lon", "name", "name_upper"]);
+    rs.push(vec![
+        MValue::Short(1),
+        MValue::NullableShort(Some(48)),
+        MValue::NullableShort(Some(8)),
+        MValue::NullableString(Some("Alice".to_string())),
+        MValue::NullableString(Some("ALICE".to_string())),
+    ]);
+    rs.push(vec![
+        MValue::Short(2),
+        MValue::NullableShort(None),
+        MValue::NullableShort(None),
+        MValue::NullableString(None),
+        MValue::NullableString(None),
+    ]);
+    let options = DeserializationOptions::default().ignore_unknown_columns(true);
+    let persons: Vec<Person> = rs.try_into_with(&options)?;
+    assert_eq!(persons[0].id, 1);
+    assert_eq!(persons[0].address, Some(Geo { lat: 48, lon: 8 }));
+    assert_eq!(persons[0].name, Some("Alice".to_string()));
+    assert_eq!(persons[1].id, 2);
+    assert_eq!(persons[1].address, None);
+    assert_eq!(persons[1].name, None);
+    Ok(())
+}
+
+fn not_nested_value(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    let s = "Negative test: no nesting into fields that are plain values";
+    info!("{}", s);
+    let mut rs = Resultset::new(&["id", "id_ext", "name"]);
+    rs.push(vec![
+        MValue::Short(1),
+        MValue::Short(2),
+        MValue::String("Alice".to_string()),
+    ]);
+    let test: mock_db::Result<Vec<User>> = rs.try_into();
+    match test {
+        Err(mock_db::Error::DESERIALIZATION(DeserializationError::UnknownField(ref f)))
+            if f == "id_ext" => {}
+        result => assert!(false, "Failed \"{}\" with {:?}", s, result),
+    }
+
+    let mut rs = Resultset::new(&["id_ext", "name"]);
+    rs.push(vec![MValue::Short(2), MValue::String("Alice".to_string())]);
+    let test: mock_db::Result<Vec<User>> = rs.try_into();
+    match test {
+        Err(mock_db::Error::DESERIALIZATION(DeserializationError::UnknownField(ref f)))
+            if f == "id_ext" => {}
+        result => assert!(false, "Failed \"{}\" with {:?}", s, result),
+    }
+
+    let options = DeserializationOptions::default().ignore_unknown_columns(true);
+    let mut rs = Resultset::new(&["id_ext", "name"]);
+    rs.push(vec![MValue::Short(2), MValue::String("Alice".to_string())]);
+    let test: mock_db::Result<Vec<User>> = rs.try_into_with(&options);
+    match test {
+        Err(mock_db::Error::DESERIALIZATION(DeserializationError::Usage(ref msg))) => {
+            info!("--> Exception: {}", msg)
+        }
+        result => assert!(false, "Failed \"{}\" with {:?}", s, result),
+    }
+    Ok(())
+}
@@ -0,0 +1,104 @@
+extern crate chrono;
+extern crate flexi_logger;
+#[macro_use]
+extern crate log;
+extern crate serde;
+extern crate serde_db;
+#[macro_use]
+extern crate serde_derive;
+
+mod mock_db;
+mod util;
+
+use crate::mock_db::{MValue as MV, Resultset};
+use flexi_logger::ReconfigurationHandle;
+
+#[test] // cargo test --test test_resultset_0xn -- --nocapture
+pub fn test_resultset_0xn() {
+    let mut loghandle = util::init_logger();
+
+    match evaluate_empty_rs(&mut loghandle) {
+        Err(e) => {
+            error!("test_resultset_0xn() failed with {:?}", e);
+            assert!(false)
+        }
+        Ok(_) => debug!("test_resultset_0xn() ended successful"),
+    }
+}
+
+// Test the various ways to evaluate an empty resultset, and the Option targets
+fn evaluate_empty_rs(loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    #[derive(Debug, Deserialize)]
+    struct TestData {
+        f1: String,
+        f2: i32,
+    }
+
+    loghandle.parse_new_spec("info");
+    info!("=== Empty resultsets (0xn) ===");
+    {
+        info!("Convert an empty resultset into an empty Vec<struct>");
+        let vd: Vec<TestData> = get_resultset(0).try_into()?;
+        assert!(vd.is_empty());
+    }
+    {
+        info!("Convert an empty resultset into Option<struct>");
+        let od: Option<TestData> = get_resultset(0).try_into()?;
+        assert!(od.is_none());
+    }
+    {
+        info!("Convert a 1x2 resultset into Option<struct>");
+        let od: Option<TestData> = get_resultset(1).try_into()?;
+        let d = od.unwrap();
+        assert_eq!(&d.f1, "0");
+        assert_eq!(d.f2, 0);
+    }
+    {
+        info!("Convert an empty resultset into Option<field>");
+        let mut rs = Resultset::new(&["f1"]);
+        let os: Option<String> = rs.try_into()?;
+        assert!(os.is_none());
+
+        rs = Resultset::new(&["f1"]);
+        rs.push(vec![MV::NullableString(None)]);
+        let os: Option<String> = rs.try_into()?;
+        assert!(os.is_none());
+
+        rs = Resultset::new(&["f1"]);
+        rs.push(vec![MV::String("a".to_string())]);
+        let os: Option<String> = rs.try_into()?;
+        assert_eq!(os, Some("a".to_string()));
+    }
+    {
+        info!("Convert an empty resultset into ()");
+        let _: () = get_resultset(0).try_into()?;
+    }
+    {
+        let s = "Negative test: no conversion of a non-empty resultset into ()";
+        info!("{}", s);
+        let test: mock_db::Result<()> = get_resultset(1).try_into();
+        assert!(test.is_err(), "Failed \"{}\"", s);
+    }
+    {
+        let s = "Negative test: no conversion of a 2x2 resultset into Option<struct>";
+        info!("{}", s);
+        let test: mock_db::Result<Option<TestData>> = get_resultset(2).try_into();
+        assert!(test.is_err(), "Failed \"{}\"", s);
+    }
+    {
+        let s = "Negative test: no conversion of an empty resultset into a struct";
+        info!("{}", s);
+        let test: mock_db::Result<TestData> = get_resultset(0).try_into();
+        assert!(test.is_err(), "Failed \"{}\"", s);
+    }
+    Ok(())
+}
+
+////////////////////////////////////////////////////////
+fn get_resultset(len: usize) -> Resultset {
+    let mut rs = Resultset::new(&["f1", "f2"]);
+    for i in 0..len {
+        rs.push(vec![MV::String(format!("{}", i)), MV::Short(i as i16)]);
+    }
+    rs
+}
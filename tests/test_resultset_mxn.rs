@@ -12,6 +12,11 @@ mod util;
 
 use crate::mock_db::{MValue, Resultset, Timestamp};
 use chrono::NaiveDateTime;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use serde_db::de::{
+    ConversionPolicy, DeserializationError, DeserializationOptions, RowCardinality,
+};
 #[allow(unused_imports)]
 use flexi_logger::{LogSpecification, ReconfigurationHandle};
 
@@ -51,6 +56,18 @@ struct ShortData {
     f2: NaiveDateTime,
     f4: Option<i32>,
 }
+#[derive(Deserialize)]
+struct RemainingData {
+    f2: NaiveDateTime,
+    f3: i32,
+    f4: Option<i32>,
+}
+#[derive(Deserialize)]
+struct ColumnData {
+    f1: Vec<Option<i32>>,
+    f2: Vec<i32>,
+    f4: Vec<i32>,
+}
 #[allow(dead_code)]
 #[derive(Deserialize)]
 struct LongData {
@@ -69,7 +86,9 @@ fn evaluate_matrix_rs(loghandle: &mut ReconfigurationHandle) -> mock_db::Result<
     not_into_struct(loghandle)?;
     not_into_field(loghandle)?;
     rows_into_struct(loghandle)?;
+    typed_iter_into_struct(loghandle)?;
     not_into_short_struct(loghandle)?;
+    lenient_into_short_struct(loghandle)?;
     rows_into_tuple(loghandle)?;
     not_rows_into_long_tuple(loghandle)?;
     rows_into_short_tuple(loghandle)?;
@@ -77,6 +96,13 @@ fn evaluate_matrix_rs(loghandle: &mut ReconfigurationHandle) -> mock_db::Result<
     pick_values_individually(loghandle)?;
     convert_values_one_by_one(loghandle)?;
     not_rows_into_value(loghandle)?;
+    conversion_error_with_location(loghandle)?;
+    not_with_policy_without_canonical(loghandle)?;
+    single_row_cardinality(loghandle)?;
+    ignore_trailing_columns(loghandle)?;
+    rows_into_maps(loghandle)?;
+    resultset_into_map(loghandle)?;
+    columns_into_struct_of_vecs(loghandle)?;
 
     Ok(())
 }
@@ -140,6 +166,44 @@ fn rows_into_struct(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<(
     assert_eq!(sum, SIZE * (SIZE + 1) / 2);
     Ok(())
 }
+fn typed_iter_into_struct(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    info!("Iterate over typed rows (streaming support)");
+    let mut sum: usize = 0;
+    for td in get_resultset_string_ts_short_short(SIZE).into_typed_iter::<TestData>() {
+        sum += td?.f3 as usize;
+    }
+    assert_eq!(sum, SIZE * (SIZE + 1) / 2);
+
+    info!("Iterate over typed rows, with options");
+    let options = DeserializationOptions::default().ignore_unknown_columns(true);
+    let rs = get_resultset_string_ts_short_short(SIZE);
+    let mut sum: usize = 0;
+    for sd in rs.into_typed_iter_with::<ShortData>(&options) {
+        sum += sd?.f4.unwrap() as usize;
+    }
+    assert_eq!(sum, 10 * SIZE * (SIZE + 1) / 2 + 7 * SIZE);
+
+    let s = "Negative test: conversion error in a single row of the typed iterator";
+    info!("{}", s);
+    let mut rs = get_resultset_string_ts_short_short(3);
+    let ts = NaiveDateTime::parse_from_str("2017-09-04 10:00:04", "%Y-%m-%d %H:%M:%S").unwrap();
+    rs.push(vec![
+        MValue::String("x".to_string()),
+        MValue::Timestamp(Timestamp(ts)),
+        MValue::Double(4.0),
+        MValue::Short(47),
+    ]);
+    let results: Vec<mock_db::Result<TestData>> = rs.into_typed_iter().collect();
+    assert_eq!(results.len(), 4);
+    assert!(results[..3].iter().all(Result::is_ok), "Failed \"{}\"", s);
+    match results[3] {
+        Err(mock_db::Error::DESERIALIZATION(DeserializationError::Located { row, .. })) => {
+            assert_eq!(row, Some(3))
+        }
+        _ => assert!(false, "Failed \"{}\"", s),
+    }
+    Ok(())
+}
 fn not_into_short_struct(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
     let s = "Negative test: no conversion of mxn resultset into Vec<too short struct>";
     info!("{}", s);
@@ -151,6 +215,26 @@ fn not_into_short_struct(_loghandle: &mut ReconfigurationHandle) -> mock_db::Res
     }
     Ok(())
 }
+fn lenient_into_short_struct(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    info!("Convert mxn resultset into Vec<too short struct>, ignoring unknown columns");
+    let ignored = Arc::new(Mutex::new(Vec::<Vec<String>>::new()));
+    let ignored_clone = Arc::clone(&ignored);
+    let options = DeserializationOptions::default()
+        .ignore_unknown_columns(true)
+        .on_ignored_columns(move |columns| ignored_clone.lock().unwrap().push(columns.to_vec()));
+    let vsd: Vec<ShortData> = get_resultset_string_ts_short_short(SIZE).try_into_with(&options)?;
+    assert_eq!(vsd.len(), SIZE);
+    assert_eq!(vsd[1].f4, Some(27));
+    assert_eq!(*ignored.lock().unwrap(), vec![vec!["f3".to_string()]]);
+
+    info!("Convert single row into too short struct, ignoring unknown columns");
+    for row in get_resultset_string_ts_short_short(1) {
+        let sd: ShortData = row.try_into_with(&options)?;
+        assert_eq!(sd.f1, "b");
+    }
+    assert_eq!(ignored.lock().unwrap().len(), 2);
+    Ok(())
+}
 fn rows_into_tuple(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
     let s = "Loop over rows, convert row into tuple";
     info!("{}", s);
@@ -233,7 +317,227 @@ fn not_rows_into_value(_loghandle: &mut ReconfigurationHandle) -> mock_db::Resul
     Ok(())
 }
 
+fn conversion_error_with_location(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    let s = "Negative test: conversion error reports row, column, and target type";
+    info!("{}", s);
+    let mut rs = get_resultset_string_ts_short_short(3);
+    let ts = NaiveDateTime::parse_from_str("2017-09-04 10:00:04", "%Y-%m-%d %H:%M:%S").unwrap();
+    rs.push(vec![
+        MValue::String("x".to_string()),
+        MValue::Timestamp(Timestamp(ts)),
+        MValue::Double(4.0),
+        MValue::Short(47),
+    ]);
+    let test: mock_db::Result<Vec<TestData>> = rs.try_into();
+    match test {
+        Err(mock_db::Error::DESERIALIZATION(e)) => {
+            info!("--> Exception: {}", e);
+            match e {
+                DeserializationError::Located {
+                    row,
+                    column,
+                    column_name,
+                    target_type,
+                    ..
+                } => {
+                    assert_eq!(row, Some(3));
+                    assert_eq!(column, Some(2));
+                    assert_eq!(column_name, Some("f3".to_string()));
+                    assert_eq!(target_type, Some("i32"));
+                }
+                _ => assert!(false, "Failed \"{}\"", s),
+            }
+        }
+        _ => assert!(false, "Failed \"{}\"", s),
+    }
+    Ok(())
+}
+
+fn not_with_policy_without_canonical(
+    _loghandle: &mut ReconfigurationHandle,
+) -> mock_db::Result<()> {
+    let s = "Negative test: no non-default policy for values without canonical representation";
+    info!("{}", s);
+    let options = DeserializationOptions::default().conversion_policy(ConversionPolicy::Strict);
+    let test: mock_db::Result<Vec<TestData>> =
+        get_resultset_string_ts_short_short(3).try_into_with(&options);
+    match test {
+        Err(mock_db::Error::DESERIALIZATION(DeserializationError::Usage(msg))) => {
+            info!("--> Exception: {}", msg)
+        }
+        _ => assert!(false, "Failed \"{}\"", s),
+    }
+    Ok(())
+}
+
 ////////////////////////////////////////////////////////
+fn single_row_cardinality(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    info!("Convert the first row of a mxn resultset into a struct");
+    let options = DeserializationOptions::default().cardinality(RowCardinality::FirstRow);
+    let td: TestData = get_resultset_string_ts_short_short(SIZE).try_into_with(&options)?;
+    assert_eq!(td.f3, 1);
+    let otd: Option<TestData> = get_resultset_string_ts_short_short(0).try_into_with(&options)?;
+    assert!(otd.is_none());
+
+    let s = "Negative test: no conversion of empty resultset into Option with ExactlyOne";
+    info!("{}", s);
+    let options = DeserializationOptions::default().cardinality(RowCardinality::ExactlyOne);
+    let test: mock_db::Result<Option<TestData>> =
+        get_resultset_string_ts_short_short(0).try_into_with(&options);
+    assert!(test.is_err(), "Failed \"{}\"", s);
+    let otd: Option<TestData> = get_resultset_string_ts_short_short(1).try_into_with(&options)?;
+    assert!(otd.is_some());
+
+    let s = "Negative test: no conversion of mxn resultset into struct with AtMostOne";
+    info!("{}", s);
+    let options = DeserializationOptions::default().cardinality(RowCardinality::AtMostOne);
+    let test: mock_db::Result<TestData> =
+        get_resultset_string_ts_short_short(2).try_into_with(&options);
+    match test {
+        Err(mock_db::Error::DESERIALIZATION(DeserializationError::TrailingRows)) => {}
+        _ => assert!(false, "Failed \"{}\"", s),
+    }
+    Ok(())
+}
+fn ignore_trailing_columns(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    info!("Convert rows into the value of their first column");
+    let options = DeserializationOptions::default().ignore_trailing_columns(true);
+    let mut values = Vec::<String>::new();
+    for row in get_resultset_string_ts_short_short(SIZE) {
+        values.push(row.try_into_with(&options)?);
+    }
+    assert_eq!(values.len(), SIZE);
+    assert_eq!(values[0], "b");
+    assert_eq!(values[1], "c");
+
+    let s = "Negative test: no conversion of row into value without ignore_trailing_columns";
+    info!("{}", s);
+    for row in get_resultset_string_ts_short_short(1) {
+        let test: mock_db::Result<String> = row.try_into();
+        match test {
+            Err(mock_db::Error::DESERIALIZATION(DeserializationError::TrailingCols)) => {}
+            _ => assert!(false, "Failed \"{}\"", s),
+        }
+    }
+    Ok(())
+}
+
+fn rows_into_maps(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    info!("Convert a mxn resultset into a Vec of maps with the column names as keys");
+    let vm: Vec<BTreeMap<String, Option<i32>>> =
+        get_resultset_option_option_short_short(SIZE).try_into()?;
+    assert_eq!(vm.len(), SIZE);
+    assert_eq!(vm[2].keys().collect::<Vec<_>>(), vec!["f1", "f2", "f3", "f4"]);
+    assert_eq!(vm[2]["f1"], None);
+    assert_eq!(vm[3]["f1"], Some(3));
+    assert_eq!(vm[3]["f4"], Some(37));
+
+    info!("Convert a row into a HashMap with the column names as keys");
+    for row in get_resultset_string_ts_short_short(1) {
+        let m: HashMap<String, String> = row.try_into()?;
+        assert_eq!(m.len(), 4);
+        assert_eq!(m["f1"], "b");
+        assert_eq!(m["f3"], "1");
+    }
+    Ok(())
+}
+
+fn resultset_into_map(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    info!("Convert a nx2 resultset into a HashMap");
+    let mut rs = Resultset::new(&["id", "name"]);
+    for i in 0..SIZE {
+        rs.push(vec![
+            MValue::Short(i as i16),
+            MValue::String(format!("name_{}", i)),
+        ]);
+    }
+    let m: HashMap<i32, String> = rs.try_into()?;
+    assert_eq!(m.len(), SIZE);
+    assert_eq!(m[&7], "name_7");
+
+    info!("Convert a 1x2 resultset into a HashMap, keyed by the first column, not by column name");
+    let mut rs = Resultset::new(&["id", "name"]);
+    rs.push(vec![MValue::String("7".to_string()), MValue::String("name_7".to_string())]);
+    let m: HashMap<String, String> = rs.try_into()?;
+    assert_eq!(m.len(), 1);
+    assert_eq!(m["7"], "name_7");
+
+    info!("Convert a nxm resultset into a map from the first column to a struct");
+    let m: BTreeMap<String, RemainingData> = get_resultset_string_ts_short_short(SIZE).try_into()?;
+    assert_eq!(m.len(), SIZE);
+    assert_eq!(m["d"].f3, 3);
+    assert_eq!(m["d"].f4, Some(37));
+    assert_eq!(m["d"].f2.to_string(), "2017-09-03 10:00:03");
+
+    info!("Convert a nxm resultset into a map from the first column to a tuple");
+    let m: HashMap<String, (NaiveDateTime, i32, i32)> =
+        get_resultset_string_ts_short_short(SIZE).try_into()?;
+    assert_eq!(m["e"].1, 4);
+
+    let s = "Negative test: no conversion of nxm resultset into map with plain values";
+    info!("{}", s);
+    let test: mock_db::Result<HashMap<String, i32>> =
+        get_resultset_string_ts_short_short(SIZE).try_into();
+    match test {
+        Ok(_) => assert!(false, "Failed \"{}\"", s),
+        Err(e) => info!("--> Exception: {:?}", e),
+    }
+
+    let s = "Negative test: no conversion of nx1 resultset into map";
+    info!("{}", s);
+    let mut rs = Resultset::new(&["id"]);
+    rs.push(vec![MValue::Short(1)]);
+    let test: mock_db::Result<HashMap<i32, i32>> = rs.try_into();
+    match test {
+        Ok(_) => assert!(false, "Failed \"{}\"", s),
+        Err(e) => info!("--> Exception: {:?}", e),
+    }
+    Ok(())
+}
+
+fn columns_into_struct_of_vecs(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    info!("Convert a mxn resultset column-wise into a struct of Vecs");
+    let options = DeserializationOptions::default().ignore_unknown_columns(true);
+    let cd: ColumnData =
+        get_resultset_option_option_short_short(SIZE).try_into_columns_with(&options)?;
+    assert_eq!(cd.f1.len(), SIZE);
+    assert_eq!(cd.f1[2], None);
+    assert_eq!(cd.f1[3], Some(3));
+    assert_eq!(cd.f2[3], 3);
+    assert_eq!(cd.f4[3], 37);
+
+    info!("Convert a mxn resultset column-wise into a map of Vecs");
+    let m: HashMap<String, Vec<Option<i32>>> =
+        get_resultset_option_option_short_short(SIZE).try_into_columns()?;
+    assert_eq!(m.len(), 4);
+    assert_eq!(m["f4"].len(), SIZE);
+    assert_eq!(m["f3"][5], Some(5));
+
+    info!("Convert an empty resultset column-wise into a struct of empty Vecs");
+    let cd: ColumnData =
+        get_resultset_option_option_short_short(0).try_into_columns_with(&options)?;
+    assert!(cd.f1.is_empty());
+
+    let s = "Negative test: no column-wise conversion with unknown column";
+    info!("{}", s);
+    let test: mock_db::Result<ColumnData> =
+        get_resultset_option_option_short_short(SIZE).try_into_columns();
+    match test {
+        Ok(_) => assert!(false, "Failed \"{}\"", s),
+        Err(e) => info!("--> Exception: {:?}", e),
+    }
+
+    let s = "Negative test: no column-wise conversion of null values into Vec<i32>";
+    info!("{}", s);
+    let test: mock_db::Result<HashMap<String, Vec<i32>>> =
+        get_resultset_option_option_short_short(SIZE).try_into_columns();
+    match test {
+        Ok(_) => assert!(false, "Failed \"{}\"", s),
+        Err(e) => info!("--> Exception: {:?}", e),
+    }
+    Ok(())
+}
+
 fn get_resultset_string_ts_short_short(len: usize) -> Resultset {
     assert!(len < 60);
     let mut rs = Resultset::new(&["f1", "f2", "f3", "f4"]);
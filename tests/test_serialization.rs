@@ -10,8 +10,10 @@ extern crate serde_derive;
 mod mock_db;
 mod util;
 
-use crate::mock_db::{MValue, ParameterType as PT};
+use crate::mock_db::{MValue, NamedParameter as NP, ParameterType as PT};
 use chrono::{NaiveDate, NaiveDateTime};
+use std::collections::BTreeMap;
+use serde_db::ser::{type_error, ConversionPolicy, DbvFactory, SerializationError};
 use flexi_logger::ReconfigurationHandle;
 
 #[test] // cargo test --test test_serialization -- --nocapture
@@ -30,6 +32,13 @@ pub fn test_serialization() {
 fn impl_test_serialization(loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
     tuple_serialization(loghandle)?;
     struct_serialization(loghandle)?;
+    not_serializable_value(loghandle)?;
+    int128_serialization(loghandle)?;
+    provided_conversions(loghandle)?;
+    conversion_policy(loghandle)?;
+    too_few_values(loghandle)?;
+    named_serialization(loghandle)?;
+    not_named_serialization(loghandle)?;
     Ok(())
 }
 
@@ -219,6 +228,367 @@ fn struct_serialization(_loghandle: &mut ReconfigurationHandle) -> mock_db::Resu
     Ok(())
 }
 
+fn not_serializable_value(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    let s = "Negative test: error reports the index and the name of the failing parameter";
+    info!("{}", s);
+    let input_metadata: Vec<PT> = vec![PT::Short, PT::String, PT::NullableShort];
+
+    #[derive(Serialize)]
+    struct Input {
+        zero: i32,
+        one: String,
+        two: Option<i32>,
+    }
+    let input = Input {
+        zero: 0,
+        one: "One".to_string(),
+        two: Some(70_000),
+    };
+    match serde_db::ser::to_params(&input, &mut input_metadata.iter()) {
+        Ok(_) => assert!(false, "Failed \"{}\"", s),
+        Err(e) => {
+            info!("--> Exception: {}", e);
+            match e {
+                SerializationError::Parameter {
+                    index, name, cause, ..
+                } => {
+                    assert_eq!(index, 2);
+                    assert_eq!(name, Some("two".to_string()));
+                    match *cause {
+                        SerializationError::Range(..) => {}
+                        _ => assert!(false, "Failed \"{}\"", s),
+                    }
+                }
+                _ => assert!(false, "Failed \"{}\"", s),
+            }
+        }
+    }
+    Ok(())
+}
+
+fn int128_serialization(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    info!("Serialize 128-bit integers with the default conversions of DbvFactory");
+    let input_metadata: Vec<PT> = vec![PT::Short, PT::NullableShort];
+    let result = serde_db::ser::to_params(&(17_i128, Some(-42_i128)), &mut input_metadata.iter())?;
+    assert!(mvalvec_compare(
+        &result,
+        &[MValue::Short(17), MValue::NullableShort(Some(-42))]
+    ));
+
+    let s = "Negative test: no serialization of an i128 that exceeds the range of i64";
+    info!("{}", s);
+    let input_metadata: Vec<PT> = vec![PT::Short];
+    match serde_db::ser::to_params(&(i128::MAX,), &mut input_metadata.iter()) {
+        Ok(_) => assert!(false, "Failed \"{}\"", s),
+        Err(SerializationError::Parameter { cause, .. }) => match *cause {
+            SerializationError::Range("i128", _) => info!("--> Exception: {}", cause),
+            _ => assert!(false, "Failed \"{}\"", s),
+        },
+        Err(_) => assert!(false, "Failed \"{}\"", s),
+    }
+    Ok(())
+}
+
+// A factory that implements only the core methods of DbvFactory
+struct CoreParam;
+impl DbvFactory for &CoreParam {
+    type DBV = MValue;
+
+    fn from_bool(&self, value: bool) -> Result<MValue, SerializationError> {
+        Ok(MValue::Short(i16::from(value)))
+    }
+    fn from_i64(&self, value: i64) -> Result<MValue, SerializationError> {
+        if value >= i64::from(i16::MIN) && value <= i64::from(i16::MAX) {
+            Ok(MValue::Short(value as i16))
+        } else {
+            Err(SerializationError::Range("i64", self.descriptor()))
+        }
+    }
+    fn from_u64(&self, value: u64) -> Result<MValue, SerializationError> {
+        if value <= i16::MAX as u64 {
+            Ok(MValue::Short(value as i16))
+        } else {
+            Err(SerializationError::Range("u64", self.descriptor()))
+        }
+    }
+    fn from_f64(&self, value: f64) -> Result<MValue, SerializationError> {
+        Ok(MValue::Double(value))
+    }
+    fn from_str(&self, value: &str) -> Result<MValue, SerializationError> {
+        Ok(MValue::String(value.to_string()))
+    }
+    fn from_bytes(&self, _value: &[u8]) -> Result<MValue, SerializationError> {
+        Err(type_error("bytes", self.descriptor()))
+    }
+    fn from_none(&self) -> Result<MValue, SerializationError> {
+        Ok(MValue::NullableShort(None))
+    }
+    fn descriptor(&self) -> String {
+        "CoreParam".to_string()
+    }
+}
+
+// A factory for a floating point parameter, which also accepts booleans
+struct FloatParam;
+impl DbvFactory for &FloatParam {
+    type DBV = MValue;
+
+    fn from_bool(&self, value: bool) -> Result<MValue, SerializationError> {
+        Ok(MValue::Short(i16::from(value)))
+    }
+    fn from_i64(&self, _value: i64) -> Result<MValue, SerializationError> {
+        Err(type_error("i64", self.descriptor()))
+    }
+    fn from_u64(&self, _value: u64) -> Result<MValue, SerializationError> {
+        Err(type_error("u64", self.descriptor()))
+    }
+    fn from_f64(&self, value: f64) -> Result<MValue, SerializationError> {
+        Ok(MValue::Double(value))
+    }
+    fn from_str(&self, _value: &str) -> Result<MValue, SerializationError> {
+        Err(type_error("str", self.descriptor()))
+    }
+    fn from_bytes(&self, _value: &[u8]) -> Result<MValue, SerializationError> {
+        Err(type_error("bytes", self.descriptor()))
+    }
+    fn from_none(&self) -> Result<MValue, SerializationError> {
+        Ok(MValue::NullableDouble(None))
+    }
+    fn descriptor(&self) -> String {
+        "FloatParam".to_string()
+    }
+}
+
+fn provided_conversions(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    info!("Serialize with the provided methods of a DbvFactory that implements only the core");
+    let input_metadata = [CoreParam, CoreParam, CoreParam, CoreParam, CoreParam, CoreParam];
+    let input = (true, -3_i8, 7_u8, 100_u32, 2.5_f32, 'x');
+    let result = serde_db::ser::to_params(&input, &mut input_metadata.iter())?;
+    assert!(mvalvec_compare(
+        &result,
+        &[
+            MValue::Short(1),
+            MValue::Short(-3),
+            MValue::Short(7),
+            MValue::Short(100),
+            MValue::Double(2.5),
+            MValue::String("x".to_string()),
+        ]
+    ));
+
+    let s = "Negative test: the provided methods check the range";
+    info!("{}", s);
+    for input in &[u64::MAX, 70_000] {
+        match serde_db::ser::to_params(&(input,), &mut [CoreParam].iter()) {
+            Ok(_) => assert!(false, "Failed \"{}\"", s),
+            Err(SerializationError::Parameter { cause, .. }) => match *cause {
+                SerializationError::Range(..) => info!("--> Exception: {}", cause),
+                _ => assert!(false, "Failed \"{}\"", s),
+            },
+            Err(_) => assert!(false, "Failed \"{}\"", s),
+        }
+    }
+    Ok(())
+}
+
+fn conversion_policy(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    info!("Serialize numbers of other kinds, if the conversion policy allows it");
+    let input_metadata: Vec<PT> = vec![PT::Short, PT::NullableShort];
+    let result =
+        serde_db::ser::to_params(&(2.0_f64, Some(-7.0_f32)), &mut input_metadata.iter())?;
+    assert!(mvalvec_compare(
+        &result,
+        &[MValue::Short(2), MValue::NullableShort(Some(-7))]
+    ));
+
+    let result = serde_db::ser::to_params_with(
+        &(2.5_f64, Some(-7.4_f32)),
+        &mut input_metadata.iter(),
+        ConversionPolicy::Lossy,
+    )?;
+    assert!(mvalvec_compare(
+        &result,
+        &[MValue::Short(3), MValue::NullableShort(Some(-7))]
+    ));
+
+    let result = serde_db::ser::to_params_with(
+        &(" 17 ", Some("-2.0")),
+        &mut input_metadata.iter(),
+        ConversionPolicy::ParseFromString,
+    )?;
+    assert!(mvalvec_compare(
+        &result,
+        &[MValue::Short(17), MValue::NullableShort(Some(-2))]
+    ));
+
+    info!("Parse strings into booleans, and into integers that are then converted into floats");
+    let input_metadata = [FloatParam, FloatParam, FloatParam, FloatParam];
+    let result = serde_db::ser::to_params_with(
+        &("42", " true", "false", "2.5"),
+        &mut input_metadata.iter(),
+        ConversionPolicy::ParseFromString,
+    )?;
+    assert!(mvalvec_compare(
+        &result,
+        &[
+            MValue::Double(42.0),
+            MValue::Short(1),
+            MValue::Short(0),
+            MValue::Double(2.5),
+        ]
+    ));
+
+    let s = "Negative test: no conversion that the conversion policy does not allow";
+    info!("{}", s);
+    use serde_db::ser::{to_params, to_params_with};
+    let short = [PT::Short];
+    let tests = vec![
+        to_params(&("17",), &mut short.iter()),
+        to_params_with(&(2.5_f64,), &mut short.iter(), ConversionPolicy::RangeChecked),
+        to_params_with(&("17",), &mut short.iter(), ConversionPolicy::Lossy),
+        to_params_with(&(2.0_f64,), &mut short.iter(), ConversionPolicy::Strict),
+        to_params_with(&(1e6_f64,), &mut short.iter(), ConversionPolicy::Lossy),
+    ];
+    for result in tests {
+        match result {
+            Ok(_) => assert!(false, "Failed \"{}\"", s),
+            Err(SerializationError::Parameter { cause, .. }) => match *cause {
+                SerializationError::Type { .. } => info!("--> Exception: {}", cause),
+                _ => assert!(false, "Failed \"{}\"", s),
+            },
+            Err(_) => assert!(false, "Failed \"{}\"", s),
+        }
+    }
+    Ok(())
+}
+
+fn too_few_values(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    let s = "Negative test: too few values for the parameters";
+    info!("{}", s);
+    let input_metadata: Vec<PT> = vec![PT::Short, PT::String, PT::NullableShort];
+    match serde_db::ser::to_params(&(5,), &mut input_metadata.iter()) {
+        Err(SerializationError::TooFewValues { expected, supplied }) => {
+            assert_eq!(expected, 3);
+            assert_eq!(supplied, 1);
+        }
+        _ => assert!(false, "Failed \"{}\"", s),
+    }
+
+    info!("Trailing parameters with default can be omitted");
+    let result: Vec<MValue> =
+        serde_db::ser::to_params(&(5, "five"), &mut input_metadata.iter())?;
+    let expected = vec![MValue::Short(5), MValue::String("five".to_string())];
+    assert!(mvalvec_compare(&expected, &result));
+
+    let input_metadata: Vec<NP> = vec![
+        NP("id", PT::Short),
+        NP("price", PT::Short),
+        NP("comment", PT::NullableString),
+    ];
+    let input = NamedInput {
+        comment: None,
+        price: 5,
+        id: 17,
+    };
+    let result: Vec<MValue> =
+        serde_db::ser::to_named_params(&input, &mut input_metadata.iter())?;
+    let expected = vec![MValue::Short(17), MValue::Short(5)];
+    assert!(mvalvec_compare(&expected, &result));
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct NamedInput {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    comment: Option<String>,
+    price: i32,
+    id: i32,
+}
+
+fn named_serialization(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    info!("Bind struct fields and map entries to parameters by name");
+    let input_metadata: Vec<NP> = vec![
+        NP("id", PT::Short),
+        NP("comment", PT::NullableString),
+        NP("price", PT::Short),
+    ];
+    let input = NamedInput {
+        comment: Some("cheap".to_string()),
+        price: 5,
+        id: 17,
+    };
+    let result: Vec<MValue> =
+        serde_db::ser::to_named_params(&input, &mut input_metadata.iter())?;
+    let expected = vec![
+        MValue::Short(17),
+        MValue::NullableString(Some("cheap".to_string())),
+        MValue::Short(5),
+    ];
+    assert!(mvalvec_compare(&expected, &result));
+
+    let mut input = BTreeMap::new();
+    input.insert("price", Some(8));
+    input.insert("id", Some(18));
+    input.insert("comment", None);
+    let result: Vec<MValue> =
+        serde_db::ser::to_named_params(&input, &mut input_metadata.iter())?;
+    let expected = vec![
+        MValue::Short(18),
+        MValue::NullableString(None),
+        MValue::Short(8),
+    ];
+    assert!(mvalvec_compare(&expected, &result));
+    Ok(())
+}
+
+fn not_named_serialization(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    let s = "Negative test: binding by name detects missing, duplicate, and unknown names";
+    info!("{}", s);
+    let input_metadata: Vec<NP> = vec![
+        NP("id", PT::Short),
+        NP("comment", PT::NullableString),
+        NP("price", PT::Short),
+    ];
+    let input = NamedInput {
+        comment: None,
+        price: 5,
+        id: 17,
+    };
+    match serde_db::ser::to_named_params(&input, &mut input_metadata.iter()) {
+        Err(SerializationError::MissingParameter(ref name)) if name == "comment" => {}
+        _ => assert!(false, "Failed \"{}\"", s),
+    }
+
+    let input = (17, 5);
+    match serde_db::ser::to_named_params(&input, &mut input_metadata.iter()) {
+        Err(e) => info!("--> Exception: {}", e),
+        Ok(_) => assert!(false, "Failed \"{}\"", s),
+    }
+
+    let input_metadata: Vec<NP> = vec![NP("id", PT::Short), NP("price", PT::Short)];
+    let input = NamedInput {
+        comment: Some("cheap".to_string()),
+        price: 5,
+        id: 17,
+    };
+    match serde_db::ser::to_named_params(&input, &mut input_metadata.iter()) {
+        Err(SerializationError::UnknownParameter(ref name)) if name == "comment" => {}
+        _ => assert!(false, "Failed \"{}\"", s),
+    }
+
+    #[derive(Serialize)]
+    struct Twice {
+        id: i32,
+        #[serde(rename = "id")]
+        id2: i32,
+    }
+    match serde_db::ser::to_named_params(&Twice { id: 1, id2: 2 }, &mut input_metadata.iter()) {
+        Err(SerializationError::DuplicateParameter(ref name)) if name == "id" => {}
+        _ => assert!(false, "Failed \"{}\"", s),
+    }
+    Ok(())
+}
+
 fn mvalvec_compare(va: &[MValue], vb: &[MValue]) -> bool {
     (va.len() == vb.len()) &&  // zip stops at the shortest
      va.iter()
@@ -41,6 +41,8 @@ fn impl_test_special_types(loghandle: &mut ReconfigurationHandle) -> mock_db::Re
     rs_rows(loghandle)?;
     rs_resultset(loghandle)?;
     rs_single_value(loghandle)?;
+    rs_byte_buf(loghandle)?;
+    rs_int128(loghandle)?;
     Ok(())
 }
 
@@ -92,6 +94,97 @@ fn rs_single_value(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()
 }
 
 ////////////////////////////////////////////////////////
+// Remembers if the bytes were handed over as owned buffer, or had to be copied.
+struct ByteBuf {
+    bytes: Vec<u8>,
+    copied: bool,
+}
+
+impl<'de> serde::de::Deserialize<'de> for ByteBuf {
+    fn deserialize<D>(deserializer: D) -> Result<ByteBuf, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        struct ByteBufVisitor;
+        impl<'de> serde::de::Visitor<'de> for ByteBufVisitor {
+            type Value = ByteBuf;
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a byte buffer")
+            }
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<ByteBuf, E> {
+                Ok(ByteBuf {
+                    bytes: v.to_vec(),
+                    copied: true,
+                })
+            }
+            fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<ByteBuf, E> {
+                Ok(ByteBuf {
+                    bytes: v,
+                    copied: false,
+                })
+            }
+        }
+        deserializer.deserialize_byte_buf(ByteBufVisitor)
+    }
+}
+
+#[derive(Deserialize)]
+struct BlobData {
+    id: i32,
+    blob: ByteBuf,
+}
+
+fn rs_byte_buf(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    info!("Deserialization of byte buffers without copying them");
+    let mut resultset = Resultset::new(&["id", "blob"]);
+    resultset.push(vec![MValue::Short(1), MValue::BorrowedBytes(b"blob_1")]);
+    resultset.push(vec![MValue::Short(2), MValue::BorrowedBytes(b"blob_2")]);
+    let data: Vec<BlobData> = resultset.try_into()?;
+    assert_eq!(data[1].id, 2);
+    assert_eq!(data[1].blob.bytes, b"blob_2");
+    assert!(!data[1].blob.copied);
+
+    let mut resultset = Resultset::new(&["blob"]);
+    resultset.push(vec![MValue::BorrowedBytes(b"blob")]);
+    let blob: ByteBuf = resultset.try_into()?;
+    assert_eq!(blob.bytes, b"blob");
+    assert!(!blob.copied);
+    Ok(())
+}
+
+#[derive(Deserialize)]
+struct Int128Data {
+    id: i128,
+    amount: Option<i128>,
+}
+
+fn rs_int128(_loghandle: &mut ReconfigurationHandle) -> mock_db::Result<()> {
+    info!("Deserialization of 128-bit integers with the default conversions of DbValue");
+    let mut resultset = Resultset::new(&["id", "amount"]);
+    resultset.push(vec![MValue::Short(1), MValue::NullableShort(Some(-100))]);
+    resultset.push(vec![MValue::Short(2), MValue::NullableShort(None)]);
+    let data: Vec<Int128Data> = resultset.try_into()?;
+    assert_eq!(data[0].id, 1);
+    assert_eq!(data[0].amount, Some(-100));
+    assert_eq!(data[1].amount, None);
+
+    let mut resultset = Resultset::new(&["id"]);
+    resultset.push(vec![MValue::Short(7)]);
+    let id: i128 = resultset.try_into()?;
+    assert_eq!(id, 7);
+
+    let s = "Negative test: no conversion into u128 if DbValueInto<u64> is not supported";
+    info!("{}", s);
+    let mut resultset = Resultset::new(&["id"]);
+    resultset.push(vec![MValue::Short(7)]);
+    let test: mock_db::Result<u128> = resultset.try_into();
+    match test {
+        Ok(_) => assert!(false, "Failed \"{}\"", s),
+        Err(e) => info!("--> Exception: {:?}", e),
+    }
+    Ok(())
+}
+
 fn get_resultset_ooff(len: usize) -> Resultset {
     let mut rs = Resultset::new(&["f1", "f2", "f3", "f4"]);
     for i in 0..len {